@@ -0,0 +1,208 @@
+use crate::CameraController;
+use bevy::prelude::*;
+
+/// Where a camera orbits around and how it looks at it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraPose {
+    pub focus: Vec3,
+    /// Where the camera sits relative to the focus, in the camera's
+    /// own frame. `Vec3::Z * radius` when it looks straight at the
+    /// focus, anything else after looking around in
+    /// [`CameraMode::Fly`](crate::CameraMode::Fly).
+    pub offset: Vec3,
+    pub rotation: Quat,
+}
+
+impl CameraPose {
+    /// A pose looking straight at `focus` from `radius` away
+    pub fn looking_at_focus(
+        focus: Vec3,
+        radius: f32,
+        rotation: Quat,
+    ) -> Self {
+        Self {
+            focus,
+            offset: Vec3::Z * radius,
+            rotation,
+        }
+    }
+
+    /// The pose of a camera at `translation`, orbiting `focus`
+    pub fn from_translation(
+        translation: Vec3,
+        rotation: Quat,
+        focus: Vec3,
+    ) -> Self {
+        Self {
+            focus,
+            offset: rotation.inverse()
+                * (translation - focus),
+            rotation,
+        }
+    }
+
+    /// The current pose of a camera, around its orbit focus
+    pub fn from_camera(
+        transform: &Transform,
+        controller: &CameraController,
+    ) -> Self {
+        Self::from_translation(
+            transform.translation,
+            transform.rotation,
+            controller.orbit_focus,
+        )
+    }
+
+    /// How far the camera is from the focus
+    pub fn radius(&self) -> f32 {
+        self.offset.length()
+    }
+
+    /// Where the camera sits for this pose
+    pub fn translation(&self) -> Vec3 {
+        self.focus + self.rotation * self.offset
+    }
+
+    /// Interpolates around the focus, so a camera looking at it keeps
+    /// looking at it for the whole way
+    pub fn lerp(&self, other: &Self, t: f32) -> Self {
+        Self {
+            focus: self.focus.lerp(other.focus, t),
+            offset: self.offset.lerp(other.offset, t),
+            rotation: self
+                .rotation
+                .slerp(other.rotation, t),
+        }
+    }
+
+    pub fn apply(
+        &self,
+        transform: &mut Transform,
+        controller: &mut CameraController,
+    ) {
+        transform.translation = self.translation();
+        transform.rotation = self.rotation;
+        controller.orbit_focus = self.focus;
+    }
+}
+
+/// Eases the attached camera between two poses.
+/// The [`CameraController`] ignores input until the tween is done and removed.
+#[derive(Component, Debug, Clone)]
+pub struct CameraTween {
+    pub from: CameraPose,
+    pub to: CameraPose,
+    /// Projections to blend between, if the projection changes too
+    pub projection: Option<(Projection, Projection)>,
+    /// Seconds the whole tween takes
+    pub duration: f32,
+    pub elapsed: f32,
+}
+
+impl CameraTween {
+    pub fn new(
+        from: CameraPose,
+        to: CameraPose,
+        duration: f32,
+    ) -> Self {
+        Self {
+            from,
+            to,
+            projection: None,
+            duration,
+            elapsed: 0.0,
+        }
+    }
+
+    pub fn with_projection(
+        mut self,
+        from: Projection,
+        to: Projection,
+    ) -> Self {
+        self.projection = Some((from, to));
+        self
+    }
+
+    /// How far along the tween is, from `0.0` to `1.0`
+    pub fn progress(&self) -> f32 {
+        if self.duration > 0.0 {
+            (self.elapsed / self.duration).clamp(0.0, 1.0)
+        } else {
+            1.0
+        }
+    }
+}
+
+pub fn animate_camera(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(
+        Entity,
+        &mut Transform,
+        &mut CameraController,
+        &mut CameraTween,
+        Option<&mut Projection>,
+    )>,
+) {
+    for (
+        entity,
+        mut transform,
+        mut controller,
+        mut tween,
+        projection,
+    ) in query.iter_mut()
+    {
+        tween.elapsed += time.delta_seconds();
+        let t = ease_in_out(tween.progress());
+        tween
+            .from
+            .lerp(&tween.to, t)
+            .apply(&mut transform, &mut controller);
+
+        if let (Some(mut projection), Some((from, to))) =
+            (projection, &tween.projection)
+        {
+            *projection = lerp_projection(from, to, t);
+        }
+
+        if tween.progress() >= 1.0 {
+            commands.entity(entity).remove::<CameraTween>();
+        }
+    }
+}
+
+/// Blends the settings of matching projections, different kinds of
+/// projection switch over at the end
+fn lerp_projection(
+    from: &Projection,
+    to: &Projection,
+    t: f32,
+) -> Projection {
+    match (from, to) {
+        (
+            Projection::Perspective(from),
+            Projection::Perspective(to),
+        ) => {
+            Projection::Perspective(PerspectiveProjection {
+                fov: from.fov + (to.fov - from.fov) * t,
+                ..to.clone()
+            })
+        }
+        (
+            Projection::Orthographic(from),
+            Projection::Orthographic(to),
+        ) => Projection::Orthographic(
+            OrthographicProjection {
+                scale: from.scale
+                    + (to.scale - from.scale) * t,
+                ..to.clone()
+            },
+        ),
+        _ if t < 1.0 => from.clone(),
+        _ => to.clone(),
+    }
+}
+
+pub(crate) fn ease_in_out(t: f32) -> f32 {
+    t * t * (3.0 - 2.0 * t)
+}
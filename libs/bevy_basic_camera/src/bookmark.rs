@@ -0,0 +1,282 @@
+use crate::{
+    orthographic_projection, CameraController,
+    CameraInputAccess, CameraInputBlock, CameraPose,
+    CameraSystem, CameraTween, InputContexts,
+};
+use bevy::{
+    asset::FileAssetIo, prelude::*, utils::HashMap,
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, path::PathBuf};
+
+/// The number keys bookmarks are stored under
+const BOOKMARK_KEYS: [(KeyCode, u8); 10] = [
+    (KeyCode::Key1, 1),
+    (KeyCode::Key2, 2),
+    (KeyCode::Key3, 3),
+    (KeyCode::Key4, 4),
+    (KeyCode::Key5, 5),
+    (KeyCode::Key6, 6),
+    (KeyCode::Key7, 7),
+    (KeyCode::Key8, 8),
+    (KeyCode::Key9, 9),
+    (KeyCode::Key0, 0),
+];
+
+/// The part of a [`Projection`] worth remembering
+#[derive(
+    Debug, Clone, Copy, PartialEq, Serialize, Deserialize,
+)]
+pub enum BookmarkProjection {
+    Perspective { fov: f32 },
+    Orthographic { scale: f32 },
+}
+
+impl BookmarkProjection {
+    pub fn from_projection(
+        projection: &Projection,
+    ) -> Self {
+        match projection {
+            Projection::Perspective(perspective) => {
+                BookmarkProjection::Perspective {
+                    fov: perspective.fov,
+                }
+            }
+            Projection::Orthographic(orthographic) => {
+                BookmarkProjection::Orthographic {
+                    scale: orthographic.scale,
+                }
+            }
+        }
+    }
+
+    /// `current` with the bookmarked settings applied to it
+    pub fn to_projection(
+        &self,
+        current: &Projection,
+    ) -> Projection {
+        match (*self, current.clone()) {
+            (
+                BookmarkProjection::Perspective { fov },
+                Projection::Perspective(perspective),
+            ) => Projection::Perspective(
+                PerspectiveProjection {
+                    fov,
+                    ..perspective
+                },
+            ),
+            (
+                BookmarkProjection::Perspective { fov },
+                _,
+            ) => Projection::Perspective(
+                PerspectiveProjection { fov, ..default() },
+            ),
+            (
+                BookmarkProjection::Orthographic { scale },
+                Projection::Orthographic(orthographic),
+            ) => Projection::Orthographic(
+                OrthographicProjection {
+                    scale,
+                    ..orthographic
+                },
+            ),
+            (
+                BookmarkProjection::Orthographic { scale },
+                _,
+            ) => Projection::Orthographic(
+                orthographic_projection(scale),
+            ),
+        }
+    }
+}
+
+/// A saved camera viewpoint
+#[derive(
+    Debug, Clone, Copy, PartialEq, Serialize, Deserialize,
+)]
+pub struct CameraBookmark {
+    pub translation: Vec3,
+    pub rotation: Quat,
+    pub focus: Vec3,
+    pub projection: Option<BookmarkProjection>,
+}
+
+impl CameraBookmark {
+    pub fn from_camera(
+        transform: &Transform,
+        controller: &CameraController,
+        projection: Option<&Projection>,
+    ) -> Self {
+        Self {
+            translation: transform.translation,
+            rotation: transform.rotation,
+            focus: controller.orbit_focus,
+            projection: projection
+                .map(BookmarkProjection::from_projection),
+        }
+    }
+
+    /// The pose to ease into when recalling the bookmark, with the
+    /// bookmarked translation, rotation and orbit focus
+    pub fn pose(&self) -> CameraPose {
+        CameraPose::from_translation(
+            self.translation,
+            self.rotation,
+            self.focus,
+        )
+    }
+}
+
+/// Camera bookmarks by number key, loaded from and saved to `path`
+#[derive(Resource, Debug, Clone)]
+pub struct CameraBookmarks {
+    pub bookmarks: HashMap<u8, CameraBookmark>,
+    /// Where the bookmarks are persisted between runs
+    pub path: PathBuf,
+    /// Hold this and press a number to store a bookmark,
+    /// press the number alone to recall it
+    pub key_store: KeyCode,
+    /// Seconds it takes to ease into a recalled bookmark
+    pub recall_duration: f32,
+}
+
+impl Default for CameraBookmarks {
+    fn default() -> Self {
+        Self {
+            bookmarks: HashMap::default(),
+            path: FileAssetIo::get_base_path()
+                .join("assets")
+                .join("camera_bookmarks.ron"),
+            key_store: KeyCode::LControl,
+            recall_duration: 0.5,
+        }
+    }
+}
+
+impl CameraBookmarks {
+    pub fn load(&mut self) {
+        let contents =
+            match std::fs::read_to_string(&self.path) {
+                Ok(contents) => contents,
+                // nothing bookmarked yet
+                Err(_) => return,
+            };
+        match ron::from_str::<BTreeMap<u8, CameraBookmark>>(
+            &contents,
+        ) {
+            Ok(bookmarks) => {
+                self.bookmarks = bookmarks.into_iter().collect()
+            }
+            Err(err) => warn!(
+                "Failed to read camera bookmarks from {:?}: {}",
+                self.path, err
+            ),
+        }
+    }
+
+    pub fn save(&self) {
+        // sorted, so the file doesn't shuffle around between saves
+        let bookmarks: BTreeMap<_, _> = self
+            .bookmarks
+            .iter()
+            .map(|(key, bookmark)| (*key, *bookmark))
+            .collect();
+        let result = ron::ser::to_string_pretty(
+            &bookmarks,
+            ron::ser::PrettyConfig::default(),
+        )
+        .map_err(|err| err.to_string())
+        .and_then(|contents| {
+            std::fs::write(&self.path, contents)
+                .map_err(|err| err.to_string())
+        });
+        if let Err(err) = result {
+            warn!(
+                "Failed to save camera bookmarks to {:?}: {}",
+                self.path, err
+            );
+        }
+    }
+}
+
+fn load_bookmarks(mut bookmarks: ResMut<CameraBookmarks>) {
+    bookmarks.load();
+}
+
+pub fn camera_bookmarks(
+    mut commands: Commands,
+    key_input: Res<Input<KeyCode>>,
+    access: CameraInputAccess,
+    mut bookmarks: ResMut<CameraBookmarks>,
+    query: Query<
+        (
+            Entity,
+            &Transform,
+            &CameraController,
+            Option<&Projection>,
+        ),
+        With<Camera>,
+    >,
+) {
+    let (entity, transform, controller, projection) =
+        match query.get_single() {
+            Ok(camera) => camera,
+            Err(_) => return,
+        };
+    if !controller.enabled || !access.keyboard() {
+        return;
+    }
+
+    for (key, number) in BOOKMARK_KEYS {
+        if !key_input.just_pressed(key) {
+            continue;
+        }
+
+        if key_input.pressed(bookmarks.key_store) {
+            bookmarks.bookmarks.insert(
+                number,
+                CameraBookmark::from_camera(
+                    transform, controller, projection,
+                ),
+            );
+            bookmarks.save();
+            info!("Stored camera bookmark {}", number);
+        } else if let Some(bookmark) =
+            bookmarks.bookmarks.get(&number)
+        {
+            let mut tween = CameraTween::new(
+                CameraPose::from_camera(
+                    transform, controller,
+                ),
+                bookmark.pose(),
+                bookmarks.recall_duration,
+            );
+            if let (Some(from), Some(to)) =
+                (projection, bookmark.projection)
+            {
+                tween = tween.with_projection(
+                    from.clone(),
+                    to.to_projection(from),
+                );
+            }
+            commands.entity(entity).insert(tween);
+        }
+    }
+}
+
+/// Number keys recall camera bookmarks, holding left control stores them.
+/// Bookmarks are kept in `assets/camera_bookmarks.ron` between runs.
+#[derive(Default)]
+pub struct CameraBookmarksPlugin;
+
+impl Plugin for CameraBookmarksPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CameraBookmarks>()
+            .init_resource::<CameraInputBlock>()
+            .init_resource::<InputContexts>()
+            .add_startup_system(load_bookmarks)
+            .add_system(
+                camera_bookmarks.label(CameraSystem::Input),
+            );
+    }
+}
@@ -0,0 +1,274 @@
+use crate::{
+    CameraController, CameraMode, CameraPath, CameraTween,
+    WalkSurface,
+};
+use bevy::{prelude::*, render::primitives::Aabb};
+
+/// Keeps an orbiting camera out of meshes. The line from the focus to
+/// the camera is swept with a sphere of `radius` against every [`Aabb`]
+/// and [`WalkSurface`], hills from its height included, and the camera
+/// is pulled in front of the first thing in the way. It moves back out
+/// once nothing is in the way.
+/// ```ignore
+///  .insert(CameraCollision {
+///      min_surface_distance: 0.5,
+///      ..default()
+///  })
+/// ```
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component)]
+pub struct CameraCollision {
+    /// Radius of the sphere swept from the focus to the camera
+    pub radius: f32,
+    /// How close the camera gets to a [`WalkSurface`]
+    pub min_surface_distance: f32,
+    /// Orbit radius to return to, set while the camera is pulled in
+    pub wanted_radius: Option<f32>,
+    /// Orbit radius the camera was pulled in to, zooming changes it and
+    /// makes the new radius the wanted one
+    pub pulled_radius: f32,
+}
+
+impl Default for CameraCollision {
+    fn default() -> Self {
+        Self {
+            radius: 0.1,
+            min_surface_distance: 0.1,
+            wanted_radius: None,
+            pulled_radius: 0.0,
+        }
+    }
+}
+
+/// Distance along the ray to where it enters the box, `None` when it
+/// misses or starts inside
+fn ray_aabb(
+    origin: Vec3,
+    direction: Vec3,
+    aabb: &Aabb,
+    global_transform: &GlobalTransform,
+    margin: f32,
+) -> Option<f32> {
+    // an affine transform keeps distances along the ray proportional,
+    // so the hit is found in the box's own space
+    let inverse = global_transform.affine().inverse();
+    let origin = inverse.transform_point3(origin);
+    let direction = inverse.transform_vector3(direction);
+    let (scale, _rotation, _translation) =
+        global_transform.to_scale_rotation_translation();
+    let half_extents = Vec3::from(aabb.half_extents)
+        + margin / scale.abs().max(Vec3::splat(1e-6));
+    let center = Vec3::from(aabb.center);
+
+    let t1 = (center - half_extents - origin) / direction;
+    let t2 = (center + half_extents - origin) / direction;
+    let enter = t1.min(t2).max_element();
+    let exit = t1.max(t2).min_element();
+    if enter < 0.0 || enter > exit {
+        return None;
+    }
+    Some(enter)
+}
+
+/// Distance along the ray to where it enters the sphere, `None` when
+/// it misses or starts inside
+fn ray_sphere(
+    origin: Vec3,
+    direction: Vec3,
+    center: Vec3,
+    radius: f32,
+) -> Option<f32> {
+    let offset = origin - center;
+    let b = offset.dot(direction);
+    let c = offset.length_squared() - radius * radius;
+    if c < 0.0 {
+        return None;
+    }
+    let discriminant = b * b - c;
+    if discriminant < 0.0 {
+        return None;
+    }
+    let t = -b - discriminant.sqrt();
+    (t >= 0.0).then_some(t)
+}
+
+/// Steps a displaced [`WalkSurface`] is marched in, and the halvings
+/// of a step a hit is narrowed down with
+const SURFACE_STEPS: usize = 64;
+const SURFACE_REFINEMENTS: usize = 16;
+
+/// Distance along the ray, up to `max_t`, to where it comes within
+/// `margin` of the ground, `None` when it doesn't or starts there.
+/// Surfaces with a height are marched in steps, so hills narrower than
+/// a step can slip through.
+fn ray_surface(
+    origin: Vec3,
+    direction: Vec3,
+    max_t: f32,
+    center: Vec3,
+    surface: &WalkSurface,
+    margin: f32,
+) -> Option<f32> {
+    if surface.height.is_none() {
+        return ray_sphere(
+            origin,
+            direction,
+            center,
+            surface.radius + margin,
+        );
+    }
+
+    let below = |t: f32| {
+        let from_center = origin + direction * t - center;
+        match from_center.try_normalize() {
+            Some(normal) => {
+                from_center.length()
+                    < surface.ground_distance(normal)
+                        + margin
+            }
+            None => true,
+        }
+    };
+    if below(0.0) {
+        return None;
+    }
+    let step = max_t / SURFACE_STEPS as f32;
+    let mut outside = 0.0;
+    for i in 1..=SURFACE_STEPS {
+        let t = step * i as f32;
+        if below(t) {
+            let mut inside = t;
+            for _ in 0..SURFACE_REFINEMENTS {
+                let middle = (outside + inside) * 0.5;
+                if below(middle) {
+                    inside = middle;
+                } else {
+                    outside = middle;
+                }
+            }
+            return Some(outside);
+        }
+        outside = t;
+    }
+    None
+}
+
+#[allow(clippy::type_complexity)]
+pub fn collide_camera(
+    occluders: Query<
+        (&Aabb, &GlobalTransform),
+        (Without<WalkSurface>, Without<CameraController>),
+    >,
+    surfaces: Query<(&WalkSurface, &GlobalTransform)>,
+    mut query: Query<(
+        &mut Transform,
+        &CameraController,
+        &mut CameraCollision,
+        Option<&CameraTween>,
+        Option<&CameraPath>,
+    )>,
+) {
+    for (
+        mut transform,
+        controller,
+        mut collision,
+        tween,
+        path,
+    ) in query.iter_mut()
+    {
+        let path_playing =
+            matches!(path, Some(path) if path.playing);
+        if !matches!(
+            controller.mode,
+            CameraMode::Orbit | CameraMode::Pan
+        ) || tween.is_some()
+            || path_playing
+        {
+            collision.wanted_radius = None;
+            continue;
+        }
+
+        let focus = controller.orbit_focus;
+        let offset = transform.translation - focus;
+        let radius = offset.length();
+        if radius <= 0.0 {
+            continue;
+        }
+        let direction = offset / radius;
+
+        // move back out, unless the radius changed since it was pulled in
+        let wanted = match collision.wanted_radius {
+            Some(wanted)
+                if (radius - collision.pulled_radius)
+                    .abs()
+                    < 1e-4 =>
+            {
+                wanted
+            }
+            _ => radius,
+        };
+
+        let mut allowed = wanted;
+        for (aabb, global_transform) in occluders.iter() {
+            if let Some(t) = ray_aabb(
+                focus,
+                direction,
+                aabb,
+                global_transform,
+                collision.radius,
+            ) {
+                allowed = allowed.min(t);
+            }
+        }
+        for (surface, global_transform) in surfaces.iter() {
+            if let Some(t) = ray_surface(
+                focus,
+                direction,
+                wanted,
+                global_transform.translation(),
+                surface,
+                collision.radius,
+            ) {
+                allowed = allowed.min(t);
+            }
+        }
+        let mut translation = focus + direction * allowed;
+
+        // never closer to a surface than min_surface_distance, this
+        // catches the cases where the focus is inside the surface
+        for (surface, global_transform) in surfaces.iter() {
+            let center = global_transform.translation();
+            let from_center = translation - center;
+            if let Some(normal) =
+                from_center.try_normalize()
+            {
+                let ground = surface
+                    .ground_distance(normal)
+                    + collision.min_surface_distance;
+                if from_center.length() < ground {
+                    translation = center + normal * ground;
+                }
+            }
+        }
+
+        // keep looking at the focus from wherever the camera ended up
+        let new_offset = translation - focus;
+        if let Some(new_direction) =
+            new_offset.try_normalize()
+        {
+            transform.rotation = Quat::from_rotation_arc(
+                direction,
+                new_direction,
+            ) * transform.rotation;
+        }
+        transform.translation = translation;
+
+        let new_radius = new_offset.length();
+        if (new_radius - wanted).abs() < 1e-4 {
+            collision.wanted_radius = None;
+        } else {
+            collision.wanted_radius = Some(wanted);
+            collision.pulled_radius = new_radius;
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use crate::{CameraController, CameraPose, CameraTween};
+use bevy::{prelude::*, render::primitives::Aabb};
+
+/// Send this event to have every [`CameraController`] focus on the
+/// entity and zoom until its [`Aabb`] fills the view
+/// ```ignore
+///  fn frame_planet(
+///      mut frame_events: EventWriter<FrameEntity>,
+///      query: Query<Entity, With<Planet>>,
+///  ) {
+///      frame_events.send(FrameEntity(query.single()));
+///  }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameEntity(pub Entity);
+
+pub fn frame_entity(
+    mut commands: Commands,
+    mut frame_events: EventReader<FrameEntity>,
+    targets: Query<(&Aabb, &GlobalTransform)>,
+    cameras: Query<(
+        Entity,
+        &Transform,
+        &CameraController,
+        Option<&Projection>,
+    )>,
+) {
+    for FrameEntity(target) in frame_events.iter() {
+        let (aabb, global_transform) =
+            match targets.get(*target) {
+                Ok(target) => target,
+                Err(_) => {
+                    warn!(
+                        "Can't frame {:?}, it has no Aabb",
+                        target
+                    );
+                    continue;
+                }
+            };
+
+        // frame the sphere around the Aabb, so the distance doesn't
+        // depend on where the camera looks from
+        let (scale, _rotation, _translation) =
+            global_transform
+                .to_scale_rotation_translation();
+        let center = global_transform
+            .transform_point(Vec3::from(aabb.center));
+        let bounding_radius =
+            (Vec3::from(aabb.half_extents) * scale)
+                .length();
+
+        for (entity, transform, controller, projection) in
+            cameras.iter()
+        {
+            let radius = match projection {
+                Some(Projection::Perspective(
+                    perspective,
+                )) => {
+                    let half_fov = perspective.fov * 0.5;
+                    let half_fov_x = (half_fov.tan()
+                        * perspective.aspect_ratio)
+                        .atan();
+                    bounding_radius
+                        / half_fov.min(half_fov_x).sin()
+                }
+                _ => bounding_radius * 2.0,
+            }
+            .clamp(
+                controller.min_radius,
+                controller.max_radius,
+            );
+
+            let from = CameraPose::from_camera(
+                transform, controller,
+            );
+            let to = CameraPose::looking_at_focus(
+                center,
+                radius,
+                from.rotation,
+            );
+            let mut tween = CameraTween::new(
+                from,
+                to,
+                controller.frame_duration,
+            );
+
+            // orthographic cameras frame by scaling instead, the
+            // distance only has to keep the entity in front of them
+            if let Some(Projection::Orthographic(
+                orthographic,
+            )) = projection
+            {
+                let view_height = (orthographic.top
+                    - orthographic.bottom)
+                    .abs();
+                if view_height > 0.0 {
+                    let mut framed = orthographic.clone();
+                    framed.scale = (2.0 * bounding_radius
+                        / view_height)
+                        .clamp(
+                            controller.min_scale,
+                            controller.max_scale,
+                        );
+                    tween = tween.with_projection(
+                        Projection::Orthographic(
+                            orthographic.clone(),
+                        ),
+                        Projection::Orthographic(framed),
+                    );
+                }
+            }
+
+            commands.entity(entity).insert(tween);
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+use std::marker::PhantomData;
+
+/// Keeps the cameras from reacting to input while set, e.g. while a UI
+/// wants the pointer or keyboard for itself
+/// ```ignore
+///  fn block_camera_input(
+///      mut egui_context: ResMut<EguiContext>,
+///      mut block: ResMut<CameraInputBlock>,
+///  ) {
+///      let ctx = egui_context.ctx_mut();
+///      block.pointer = ctx.wants_pointer_input();
+///      block.keyboard = ctx.wants_keyboard_input();
+///  }
+/// ```
+#[derive(
+    Resource, Debug, Default, Clone, Copy, PartialEq, Eq,
+)]
+pub struct CameraInputBlock {
+    /// Ignore the mouse, scroll wheel and touches
+    pub pointer: bool,
+    /// Ignore the keyboard
+    pub keyboard: bool,
+}
+
+/// A stack of named input contexts, like `"camera"`,
+/// `"object-manipulation"` or `"inspector"`. Only the context on top
+/// receives input, so contexts can share bindings without stepping on
+/// each other. With nothing on the stack every context receives input.
+/// ```ignore
+///  fn move_objects(contexts: Res<InputContexts>, ..) {
+///      if !contexts.is_active("object-manipulation") {
+///          return;
+///      }
+///      ..
+///  }
+/// ```
+#[derive(
+    Resource, Debug, Default, Clone, PartialEq, Eq,
+)]
+pub struct InputContexts {
+    /// Bottom to top
+    pub stack: Vec<String>,
+}
+
+impl InputContexts {
+    /// The context the cameras listen to
+    pub const CAMERA: &'static str = "camera";
+
+    /// Puts `name` on top, moving it there if it's already on the stack
+    pub fn push(&mut self, name: impl Into<String>) {
+        let name = name.into();
+        self.remove(&name);
+        self.stack.push(name);
+    }
+
+    pub fn pop(&mut self) -> Option<String> {
+        self.stack.pop()
+    }
+
+    pub fn remove(&mut self, name: &str) {
+        self.stack.retain(|context| context != name);
+    }
+
+    pub fn top(&self) -> Option<&str> {
+        self.stack.last().map(String::as_str)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.stack.iter().any(|context| context == name)
+    }
+
+    /// Whether `name` receives input
+    pub fn is_active(&self, name: &str) -> bool {
+        self.top().map_or(true, |top| top == name)
+    }
+}
+
+/// Whether the cameras may use the pointer and keyboard, going by the
+/// [`CameraInputBlock`] and the [`InputContexts`]
+#[derive(SystemParam)]
+pub struct CameraInputAccess<'w, 's> {
+    block: Res<'w, CameraInputBlock>,
+    contexts: Res<'w, InputContexts>,
+    #[system_param(ignore)]
+    marker: PhantomData<&'s ()>,
+}
+
+impl CameraInputAccess<'_, '_> {
+    pub fn pointer(&self) -> bool {
+        !self.block.pointer
+            && self
+                .contexts
+                .is_active(InputContexts::CAMERA)
+    }
+
+    pub fn keyboard(&self) -> bool {
+        !self.block.keyboard
+            && self
+                .contexts
+                .is_active(InputContexts::CAMERA)
+    }
+}
@@ -1,20 +1,88 @@
 // originally from Griffin
 use bevy::{
-    input::mouse::{
-        MouseMotion, MouseScrollUnit, MouseWheel,
+    input::{
+        mouse::{MouseMotion, MouseScrollUnit, MouseWheel},
+        touch::TouchInput,
     },
     prelude::*,
+    render::camera::{RenderTarget, ScalingMode},
 };
+use std::f32::consts::{FRAC_PI_2, PI};
+
+mod animation;
+pub use animation::*;
+
+mod bookmark;
+pub use bookmark::*;
+
+mod collision;
+pub use collision::*;
+
+mod frame;
+pub use frame::*;
+
+mod input;
+pub use input::*;
+
+mod path;
+pub use path::*;
+
+mod settings;
+pub use settings::*;
+
+mod touch;
+pub use touch::*;
+
+mod walk;
+pub use walk::*;
+
+/// The way a [`CameraController`] reacts to mouse input
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Reflect,
+    FromReflect,
+)]
+pub enum CameraMode {
+    /// Look around freely, the camera rotates in place
+    #[default]
+    Fly,
+    /// Turntable orbit around the `orbit_focus`
+    Orbit,
+    /// Slide the camera and its `orbit_focus` along the view plane
+    Pan,
+    /// First person on the nearest [`WalkSurface`], with gravity and
+    /// jumping. Without a surface the camera moves like in `Fly` mode,
+    /// but stays level.
+    Walk,
+}
+
+impl CameraMode {
+    /// The mode that follows this one when cycling through them
+    pub fn next(self) -> Self {
+        match self {
+            CameraMode::Fly => CameraMode::Orbit,
+            CameraMode::Orbit => CameraMode::Pan,
+            CameraMode::Pan => CameraMode::Walk,
+            CameraMode::Walk => CameraMode::Fly,
+        }
+    }
+}
 
 /// Provides basic movement functionality to the attached camera
-/// ```
+/// ```ignore
 ///  .insert(CameraController {
-///    orbit_mode: true,
+///    mode: CameraMode::Orbit,
 ///    orbit_focus: Vec3::new(0.0, 0.5, 0.0),
 ///    ..default()
 ///  })
 /// ```
-#[derive(Component)]
+#[derive(Component, Reflect)]
+#[reflect(Component)]
 pub struct CameraController {
     pub enabled: bool,
     pub initialized: bool,
@@ -26,17 +94,69 @@ pub struct CameraController {
     pub key_up: KeyCode,
     pub key_down: KeyCode,
     pub key_run: KeyCode,
+    pub key_cycle_mode: KeyCode,
+    pub key_orbit_left: KeyCode,
+    pub key_orbit_right: KeyCode,
+    pub key_orbit_up: KeyCode,
+    pub key_orbit_down: KeyCode,
+    pub key_zoom_in: KeyCode,
+    pub key_zoom_out: KeyCode,
+    pub key_toggle_projection: KeyCode,
     pub mouse_key_enable_mouse: MouseButton,
+    pub mouse_key_pan: MouseButton,
     pub keyboard_key_enable_mouse: KeyCode,
+    pub key_jump: KeyCode,
+    pub key_roll_left: KeyCode,
+    pub key_roll_right: KeyCode,
     pub walk_speed: f32,
     pub run_speed: f32,
     pub friction: f32,
-    pub pitch: f32,
-    pub yaw: f32,
+    /// The direction the camera stays upright against. It can change
+    /// every frame, e.g. to point away from the centre of a planet.
+    pub up: Vec3,
+    /// Rotate freely around the camera's own axes instead of staying
+    /// upright, which also enables rolling
+    pub six_dof: bool,
+    /// Radians per second while holding a roll key
+    pub roll_speed: f32,
+    /// How far above a [`WalkSurface`] the camera stays in `Walk` mode
+    pub eye_height: f32,
+    /// Acceleration towards a [`WalkSurface`]
+    pub gravity: f32,
+    /// Speed away from the surface at the start of a jump
+    pub jump_speed: f32,
+    /// Speed along the up vector while walking, negative when falling
+    pub vertical_speed: f32,
+    /// Set while standing on a [`WalkSurface`]
+    pub grounded: bool,
     pub velocity: Vec3,
     pub orbit_focus: Vec3,
-    pub orbit_mode: bool,
+    pub mode: CameraMode,
     pub scroll_wheel_speed: f32,
+    /// Pixels of trackpad scrolling that count as one scroll wheel step
+    pub scroll_pixels_per_line: f32,
+    /// Scroll steps per second while holding a zoom key
+    pub keyboard_zoom_speed: f32,
+    /// Radians per second while holding an orbit key
+    pub keyboard_orbit_speed: f32,
+    pub min_radius: f32,
+    pub max_radius: f32,
+    /// Zoom limits for orthographic projections
+    pub min_scale: f32,
+    pub max_scale: f32,
+    /// Field of view to return to when switching back from orthographic
+    pub perspective_fov: f32,
+    /// Seconds it takes to ease the camera into a new mode
+    pub transition_time: f32,
+    /// Seconds it takes to frame an entity, see [`FrameEntity`]
+    pub frame_duration: f32,
+    /// Progress of the current mode transition, `1.0` when settled
+    pub transition_progress: f32,
+    pub transition_from: Quat,
+    pub last_mode: CameraMode,
+    /// Set when orbiting starts, so dragging horizontally is not inverted
+    /// while the camera is upside down
+    pub upside_down: bool,
 }
 
 impl Default for CameraController {
@@ -52,59 +172,411 @@ impl Default for CameraController {
             key_up: KeyCode::E,
             key_down: KeyCode::Q,
             key_run: KeyCode::LShift,
+            key_cycle_mode: KeyCode::Tab,
+            key_orbit_left: KeyCode::Left,
+            key_orbit_right: KeyCode::Right,
+            key_orbit_up: KeyCode::Up,
+            key_orbit_down: KeyCode::Down,
+            key_zoom_in: KeyCode::Equals,
+            key_zoom_out: KeyCode::Minus,
+            key_toggle_projection: KeyCode::O,
             mouse_key_enable_mouse: MouseButton::Left,
+            mouse_key_pan: MouseButton::Middle,
             keyboard_key_enable_mouse: KeyCode::M,
+            key_jump: KeyCode::Space,
+            key_roll_left: KeyCode::Z,
+            key_roll_right: KeyCode::C,
             walk_speed: 5.0,
             run_speed: 15.0,
             friction: 0.5,
-            pitch: 0.0,
-            yaw: 0.0,
+            up: Vec3::Y,
+            six_dof: false,
+            roll_speed: PI / 2.0,
+            eye_height: 1.7,
+            gravity: 9.81,
+            jump_speed: 4.0,
+            vertical_speed: 0.0,
+            grounded: false,
             velocity: Vec3::ZERO,
             orbit_focus: Vec3::ZERO,
-            orbit_mode: false,
+            mode: CameraMode::Fly,
             scroll_wheel_speed: 0.1,
+            scroll_pixels_per_line: 100.0,
+            keyboard_zoom_speed: 10.0,
+            keyboard_orbit_speed: PI / 2.0,
+            min_radius: 0.1,
+            max_radius: 1000.0,
+            min_scale: 0.001,
+            max_scale: 1000.0,
+            perspective_fov: PI / 4.0,
+            transition_time: 0.3,
+            frame_duration: 0.5,
+            transition_progress: 1.0,
+            transition_from: Quat::IDENTITY,
+            last_mode: CameraMode::Fly,
+            upside_down: false,
         }
     }
 }
 
+impl CameraController {
+    /// The distance between the camera and the point it orbits around
+    pub fn orbit_radius(
+        &self,
+        transform: &Transform,
+    ) -> f32 {
+        transform.translation.distance(self.orbit_focus)
+    }
+
+    /// The normalized `up` vector, world y if it's zero
+    pub fn up_direction(&self) -> Vec3 {
+        self.up.try_normalize().unwrap_or(Vec3::Y)
+    }
+
+    /// Turns the camera in place, `delta.x` rotates around the up
+    /// vector and `delta.y` tilts the view. Both are in radians.
+    /// Tilting stops just short of looking straight along the up
+    /// vector, unless the controller is `six_dof`.
+    pub fn look(
+        &mut self,
+        transform: &mut Transform,
+        delta: Vec2,
+    ) {
+        if self.six_dof {
+            transform.rotation = transform.rotation
+                * Quat::from_rotation_y(-delta.x)
+                * Quat::from_rotation_x(-delta.y);
+            return;
+        }
+
+        let up = self.up_direction();
+        let forward = Quat::from_axis_angle(up, -delta.x)
+            * transform.forward();
+        let right = forward
+            .cross(up)
+            .try_normalize()
+            .unwrap_or_else(|| transform.right());
+        // angle between the view and the up vector, 0 looking straight up
+        let tilt = forward.angle_between(up);
+        let min_tilt = 0.01 * FRAC_PI_2;
+        let new_tilt =
+            (tilt + delta.y).clamp(min_tilt, PI - min_tilt);
+        let forward =
+            Quat::from_axis_angle(right, tilt - new_tilt)
+                * forward;
+        transform
+            .look_at(transform.translation + forward, up);
+    }
+
+    /// Rolls the view upright against the up vector, for when the up
+    /// vector changed underneath the camera
+    pub fn level(&self, transform: &mut Transform) {
+        let forward = transform.forward();
+        let up = self.up_direction();
+        if forward.cross(up).length_squared() > 1e-6 {
+            transform.look_at(
+                transform.translation + forward,
+                up,
+            );
+        }
+    }
+
+    /// Turntable orbit around the focus, `delta.x` rotates around
+    /// the up vector and `delta.y` around the camera's x axis.
+    /// Both are in radians.
+    pub fn orbit(
+        &mut self,
+        transform: &mut Transform,
+        delta: Vec2,
+    ) {
+        let delta_x = if self.upside_down {
+            -delta.x
+        } else {
+            delta.x
+        };
+        let yaw = Quat::from_axis_angle(
+            self.up_direction(),
+            -delta_x,
+        );
+        let pitch = Quat::from_rotation_x(-delta.y);
+        // rotate around the up vector, then around local x axis
+        transform.rotation =
+            yaw * transform.rotation * pitch;
+
+        // emulating parent/child to make the yaw/y-axis rotation behave like a turntable
+        // parent = x and y rotation
+        // child = z-offset
+        let radius = self.orbit_radius(transform);
+        let rot_matrix =
+            Mat3::from_quat(transform.rotation);
+        transform.translation = self.orbit_focus
+            + rot_matrix
+                .mul_vec3(Vec3::new(0.0, 0.0, radius));
+    }
+
+    /// Moves both the camera and its focus by `offset`
+    pub fn pan(
+        &mut self,
+        transform: &mut Transform,
+        offset: Vec3,
+    ) {
+        transform.translation += offset;
+        self.orbit_focus += offset;
+    }
+
+    /// Pans so the focus follows the cursor, `delta` is in logical pixels
+    /// of a view sized `viewport`
+    pub fn pan_by_pixels(
+        &mut self,
+        transform: &mut Transform,
+        projection: Option<&Projection>,
+        delta: Vec2,
+        viewport: Vec2,
+    ) {
+        let radius = self.orbit_radius(transform);
+        // world units covered by the height of the view at the focus
+        let view_height = match projection {
+            Some(Projection::Perspective(perspective)) => {
+                2.0 * radius * (perspective.fov * 0.5).tan()
+            }
+            Some(Projection::Orthographic(
+                orthographic,
+            )) => {
+                (orthographic.top - orthographic.bottom)
+                    * orthographic.scale
+            }
+            None => radius.max(1.0),
+        };
+        let offset = (-delta.x * transform.right()
+            + delta.y * transform.up())
+            * view_height
+            / viewport.y;
+        self.pan(transform, offset);
+    }
+
+    /// Moves the camera towards the focus, one `scroll` step covers
+    /// `scroll_wheel_speed` of the remaining distance.
+    /// Orthographic cameras scale their projection instead.
+    pub fn zoom(
+        &mut self,
+        transform: &mut Transform,
+        projection: Option<&mut Projection>,
+        scroll: f32,
+    ) {
+        let factor = 1.0 - scroll * self.scroll_wheel_speed;
+        if let Some(Projection::Orthographic(
+            orthographic,
+        )) = projection
+        {
+            orthographic.scale = (orthographic.scale
+                * factor)
+                .clamp(self.min_scale, self.max_scale);
+            return;
+        }
+
+        let radius = self.orbit_radius(transform);
+        if radius <= 0.0 {
+            return;
+        }
+        let new_radius = (radius * factor)
+            .clamp(self.min_radius, self.max_radius);
+        let direction = (transform.translation
+            - self.orbit_focus)
+            / radius;
+        transform.translation =
+            self.orbit_focus + direction * new_radius;
+    }
+
+    /// Switches between perspective and orthographic, keeping the
+    /// focus the same size on screen
+    pub fn toggle_projection(
+        &mut self,
+        transform: &mut Transform,
+        projection: &mut Projection,
+    ) {
+        match projection {
+            Projection::Perspective(perspective) => {
+                // height of the view at the focus
+                let view_height = 2.0
+                    * self.orbit_radius(transform)
+                    * (perspective.fov * 0.5).tan();
+                self.perspective_fov = perspective.fov;
+                *projection = Projection::Orthographic(
+                    orthographic_projection(
+                        (view_height * 0.5).clamp(
+                            self.min_scale,
+                            self.max_scale,
+                        ),
+                    ),
+                );
+            }
+            Projection::Orthographic(orthographic) => {
+                let view_height = (orthographic.top
+                    - orthographic.bottom)
+                    * orthographic.scale;
+                let fov = self.perspective_fov;
+                let radius = (view_height
+                    / (2.0 * (fov * 0.5).tan()))
+                .clamp(self.min_radius, self.max_radius);
+                transform.translation = self.orbit_focus
+                    + transform.back() * radius;
+                *projection = Projection::Perspective(
+                    PerspectiveProjection {
+                        fov,
+                        ..default()
+                    },
+                );
+            }
+        }
+    }
+
+    /// The rotation the camera settles on when entering `mode`
+    fn target_rotation(
+        &self,
+        transform: &Transform,
+        mode: CameraMode,
+    ) -> Quat {
+        match mode {
+            CameraMode::Orbit
+                if self.orbit_radius(transform) > 0.0 =>
+            {
+                transform
+                    .looking_at(
+                        self.orbit_focus,
+                        self.up_direction(),
+                    )
+                    .rotation
+            }
+            _ => transform.rotation,
+        }
+    }
+}
+
+#[allow(
+    clippy::too_many_arguments,
+    clippy::type_complexity
+)]
 pub fn camera_controller(
     time: Res<Time>,
+    windows: Option<Res<Windows>>,
     mut mouse_events: EventReader<MouseMotion>,
     mouse_button_input: Res<Input<MouseButton>>,
     mut scroll_evr: EventReader<MouseWheel>,
     key_input: Res<Input<KeyCode>>,
+    mut touch_events: EventReader<TouchInput>,
+    access: CameraInputAccess,
     mut move_toggled: Local<bool>,
-    mut query: Query<
-        (&mut Transform, &mut CameraController),
-        With<Camera>,
-    >,
+    mut touch_tracker: Local<TouchTracker>,
+    mut query: Query<(
+        &mut Transform,
+        &mut CameraController,
+        Option<&mut Projection>,
+        Option<&CameraTween>,
+        Option<&CameraPath>,
+        &Camera,
+    )>,
 ) {
     let dt = time.delta_seconds();
 
-    if let Ok((mut transform, mut options)) =
-        query.get_single_mut()
+    let mut scroll_lines = 0.0;
+    let mut scroll_pixels = 0.0;
+    for ev in scroll_evr.iter() {
+        match ev.unit {
+            MouseScrollUnit::Line => {
+                scroll_lines += ev.y;
+            }
+            // trackpads scroll by pixels
+            MouseScrollUnit::Pixel => {
+                scroll_pixels += ev.y;
+            }
+        }
+    }
+    let gesture = touch_tracker.update(touch_events.iter());
+    let mut mouse_delta = Vec2::ZERO;
+    for mouse_event in mouse_events.iter() {
+        mouse_delta += mouse_event.delta;
+    }
+
+    // read the events either way, so they don't pile up until unblocked
+    let no_keys = Input::default();
+    let key_input = if !access.keyboard() {
+        &no_keys
+    } else {
+        &*key_input
+    };
+    let no_buttons = Input::default();
+    let (mouse_button_input, mouse_delta, gesture) =
+        if !access.pointer() {
+            scroll_lines = 0.0;
+            scroll_pixels = 0.0;
+            (
+                &no_buttons,
+                Vec2::ZERO,
+                TouchGesture::default(),
+            )
+        } else {
+            (&*mouse_button_input, mouse_delta, gesture)
+        };
+
+    for (
+        mut transform,
+        mut options,
+        mut projection,
+        tween,
+        path,
+        camera,
+    ) in query.iter_mut()
     {
         if !options.initialized {
-            let (_roll, yaw, pitch) =
-                transform.rotation.to_euler(EulerRot::ZYX);
-            options.yaw = yaw;
-            options.pitch = pitch;
+            options.last_mode = options.mode;
             options.initialized = true;
         }
-        if !options.enabled {
-            return;
+        let path_playing =
+            matches!(path, Some(path) if path.playing);
+        if !options.enabled
+            || tween.is_some()
+            || path_playing
+            || !has_focus(camera, windows.as_deref())
+        {
+            continue;
         }
 
-        let mut scroll_distance = 0.0;
+        // Handle mode switching
+        if key_input.just_pressed(options.key_cycle_mode) {
+            options.mode = options.mode.next();
+        }
+        if options.mode != options.last_mode {
+            options.last_mode = options.mode;
+            options.transition_from = transform.rotation;
+            options.transition_progress = 0.0;
+        }
+        if options.transition_progress < 1.0 {
+            options.transition_progress =
+                if options.transition_time > 0.0 {
+                    (options.transition_progress
+                        + dt / options.transition_time)
+                        .min(1.0)
+                } else {
+                    1.0
+                };
 
-        // Handle scroll input
-        for ev in scroll_evr.iter() {
-            match ev.unit {
-                MouseScrollUnit::Line => {
-                    scroll_distance = ev.y;
-                }
-                MouseScrollUnit::Pixel => (),
-            }
+            let target = options
+                .target_rotation(&transform, options.mode);
+            transform.rotation =
+                options.transition_from.slerp(
+                    target,
+                    ease_in_out(
+                        options.transition_progress,
+                    ),
+                );
+        }
+
+        let mut scroll_distance = scroll_lines
+            + gesture
+                .zoom_steps(options.scroll_wheel_speed);
+        if options.scroll_pixels_per_line > 0.0 {
+            scroll_distance += scroll_pixels
+                / options.scroll_pixels_per_line;
         }
 
         // Handle key input
@@ -132,6 +604,48 @@ pub fn camera_controller(
         {
             *move_toggled = !*move_toggled;
         }
+        if key_input
+            .just_pressed(options.key_toggle_projection)
+        {
+            if let Some(projection) =
+                projection.as_deref_mut()
+            {
+                options.toggle_projection(
+                    &mut transform,
+                    projection,
+                );
+            }
+        }
+
+        let mut roll_input = 0.0;
+        if key_input.pressed(options.key_roll_left) {
+            roll_input += 1.0;
+        }
+        if key_input.pressed(options.key_roll_right) {
+            roll_input -= 1.0;
+        }
+
+        let mut orbit_input = Vec2::ZERO;
+        if key_input.pressed(options.key_orbit_left) {
+            orbit_input.x -= 1.0;
+        }
+        if key_input.pressed(options.key_orbit_right) {
+            orbit_input.x += 1.0;
+        }
+        if key_input.pressed(options.key_orbit_up) {
+            orbit_input.y -= 1.0;
+        }
+        if key_input.pressed(options.key_orbit_down) {
+            orbit_input.y += 1.0;
+        }
+        if key_input.pressed(options.key_zoom_in) {
+            scroll_distance +=
+                options.keyboard_zoom_speed * dt;
+        }
+        if key_input.pressed(options.key_zoom_out) {
+            scroll_distance -=
+                options.keyboard_zoom_speed * dt;
+        }
 
         // Apply movement update
         if axis_input != Vec3::ZERO {
@@ -152,82 +666,220 @@ pub fn camera_controller(
         }
         let forward = transform.forward();
         let right = transform.right();
-        let translation_delta =
+        let up = if options.six_dof {
+            transform.up()
+        } else {
+            options.up_direction()
+        };
+        let translation_delta = if options.mode
+            == CameraMode::Walk
+        {
+            // walk along the ground, jumping and falling is up to
+            // walk_camera
+            let forward = (forward - up * forward.dot(up))
+                .normalize_or_zero();
+            let right = (right - up * right.dot(up))
+                .normalize_or_zero();
+            options.velocity.x * dt * right
+                + options.velocity.z * dt * forward
+        } else {
             options.velocity.x * dt * right
-                + options.velocity.y * dt * Vec3::Y
-                + options.velocity.z * dt * forward;
-        let mut scroll_translation = Vec3::ZERO;
-        if options.orbit_mode
-            && options.scroll_wheel_speed > 0.0
+                + options.velocity.y * dt * up
+                + options.velocity.z * dt * forward
+        };
+        transform.translation += translation_delta;
+        options.orbit_focus += translation_delta;
+
+        if !matches!(
+            options.mode,
+            CameraMode::Fly | CameraMode::Walk
+        ) && scroll_distance != 0.0
         {
-            scroll_translation = scroll_distance
-                * transform
-                    .translation
-                    .distance(options.orbit_focus)
-                * options.scroll_wheel_speed
-                * forward;
+            options.zoom(
+                &mut transform,
+                projection.as_deref_mut(),
+                scroll_distance,
+            );
         }
-        transform.translation +=
-            translation_delta + scroll_translation;
-        options.orbit_focus += translation_delta;
 
         // Handle mouse input
-        let mut mouse_delta = Vec2::ZERO;
-        if mouse_button_input
-            .pressed(options.mouse_key_enable_mouse)
-            || *move_toggled
+        let mouse_key = options.mouse_key_enable_mouse;
+        if mouse_button_input.just_pressed(mouse_key)
+            || mouse_button_input.just_released(mouse_key)
         {
-            for mouse_event in mouse_events.iter() {
-                mouse_delta += mouse_event.delta;
-            }
+            // only check for upside down when orbiting started or ended this frame
+            let up = transform.rotation * Vec3::Y;
+            options.upside_down =
+                up.dot(options.up_direction()) <= 0.0;
         }
 
-        if mouse_delta != Vec2::ZERO {
-            let sensitivity = if options.orbit_mode {
-                options.sensitivity * 2.0
+        let (mut look_delta, mut pan_delta) =
+            if mouse_button_input
+                .pressed(options.mouse_key_pan)
+            {
+                (Vec2::ZERO, mouse_delta)
+            } else if mouse_button_input.pressed(mouse_key)
+                || *move_toggled
+            {
+                (mouse_delta, Vec2::ZERO)
             } else {
-                options.sensitivity
+                (Vec2::ZERO, Vec2::ZERO)
             };
-            let (pitch, yaw) = (
-                (options.pitch
-                    - mouse_delta.y
-                        * 0.5
-                        * sensitivity
-                        * dt)
-                    .clamp(
-                        -0.99 * std::f32::consts::FRAC_PI_2,
-                        0.99 * std::f32::consts::FRAC_PI_2,
-                    ),
-                options.yaw
-                    - mouse_delta.x * sensitivity * dt,
-            );
+        // one finger drags like the mouse, two fingers pan
+        look_delta += gesture.drag;
+        pan_delta += gesture.pan;
 
-            // Apply look update
-            transform.rotation = Quat::from_euler(
-                EulerRot::ZYX,
-                0.0,
-                yaw,
-                pitch,
+        // let the transition finish before taking over again
+        if options.transition_progress < 1.0 {
+            continue;
+        }
+
+        if options.six_dof && roll_input != 0.0 {
+            transform.rotation *= Quat::from_rotation_z(
+                roll_input * options.roll_speed * dt,
             );
-            options.pitch = pitch;
-            options.yaw = yaw;
-
-            if options.orbit_mode {
-                let rot_matrix =
-                    Mat3::from_quat(transform.rotation);
-                transform.translation = options.orbit_focus
-                    + rot_matrix.mul_vec3(Vec3::new(
-                        0.0,
-                        0.0,
-                        options.orbit_focus.distance(
-                            transform.translation,
-                        ),
-                    ));
+        } else if matches!(
+            options.mode,
+            CameraMode::Fly | CameraMode::Walk
+        ) && !options.six_dof
+        {
+            options.level(&mut transform);
+        }
+
+        // turning two fingers turns the scene along with them
+        let orbit_delta =
+            orbit_input * options.keyboard_orbit_speed * dt
+                + Vec2::new(-gesture.rotate, 0.0);
+        if options.mode == CameraMode::Orbit
+            && orbit_delta != Vec2::ZERO
+        {
+            options.orbit(&mut transform, orbit_delta);
+        }
+
+        // dragging is relative to the size of the view, looking
+        // around doesn't need it
+        let viewport =
+            viewport_size(camera, windows.as_deref());
+
+        if pan_delta != Vec2::ZERO {
+            if let Some(viewport) = viewport {
+                options.pan_by_pixels(
+                    &mut transform,
+                    projection.as_deref(),
+                    pan_delta,
+                    viewport,
+                );
             }
         }
+
+        if look_delta == Vec2::ZERO {
+            continue;
+        }
+
+        match (options.mode, viewport) {
+            (CameraMode::Fly | CameraMode::Walk, _) => {
+                let delta = Vec2::new(
+                    look_delta.x,
+                    look_delta.y * 0.5,
+                ) * options.sensitivity
+                    * dt;
+                options.look(&mut transform, delta);
+            }
+            (CameraMode::Orbit, Some(viewport)) => {
+                let delta = Vec2::new(
+                    look_delta.x / viewport.x * PI * 2.0,
+                    look_delta.y / viewport.y * PI,
+                );
+                options.orbit(&mut transform, delta);
+            }
+            (CameraMode::Pan, Some(viewport)) => {
+                options.pan_by_pixels(
+                    &mut transform,
+                    projection.as_deref(),
+                    look_delta,
+                    viewport,
+                );
+            }
+            // no viewport to scale the drag by yet
+            _ => {}
+        }
+    }
+}
+
+/// The orthographic projection the controller switches to, `scale`
+/// world units above and below the center of the view whatever the
+/// window size
+pub(crate) fn orthographic_projection(
+    scale: f32,
+) -> OrthographicProjection {
+    OrthographicProjection {
+        scaling_mode: ScalingMode::FixedVertical(2.0),
+        scale,
+        ..default()
+    }
+}
+
+/// Logical size of what the camera renders to, be it a window or an image
+fn viewport_size(
+    camera: &Camera,
+    windows: Option<&Windows>,
+) -> Option<Vec2> {
+    camera
+        .logical_viewport_size()
+        .or_else(|| match &camera.target {
+            // not computed by the renderer yet
+            RenderTarget::Window(id) => windows
+                .and_then(|windows| windows.get(*id))
+                .map(|window| {
+                    Vec2::new(
+                        window.width(),
+                        window.height(),
+                    )
+                }),
+            RenderTarget::Image(_) => None,
+        })
+        .filter(|size| size.x > 0.0 && size.y > 0.0)
+}
+
+/// Only cameras rendering to the focused window take input, so each
+/// window can have its own controller. Cameras rendering to an image, or
+/// to a window that doesn't exist (yet), are always controlled.
+fn has_focus(
+    camera: &Camera,
+    windows: Option<&Windows>,
+) -> bool {
+    match &camera.target {
+        RenderTarget::Window(id) => windows
+            .and_then(|windows| windows.get(*id))
+            .map_or(true, Window::is_focused),
+        RenderTarget::Image(_) => true,
     }
 }
 
+/// Labels for ordering your own systems around the camera's.
+/// They all run in [`CoreStage::Update`], so they are done before
+/// [`TransformSystem::TransformPropagate`](bevy::transform::TransformSystem::TransformPropagate)
+/// and the camera is drawn where it was moved to the same frame.
+/// ```ignore
+///  .add_system(move_components.before(CameraSystem::Input))
+/// ```
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, SystemLabel,
+)]
+pub enum CameraSystem {
+    /// Reads input and moves the cameras, including bookmark, path and
+    /// settings keys
+    Input,
+    /// Keeps walking cameras on the ground
+    Walk,
+    /// Plays camera tweens, e.g. from framing an entity
+    Animate,
+    /// Advances camera paths
+    Paths,
+    /// Pulls cameras out of meshes, after everything else moved them
+    Collide,
+}
+
 /// Simple flying camera plugin.
 /// In order to function, the [`CameraController`] component should be attached to the camera entity.
 #[derive(Default)]
@@ -235,6 +887,35 @@ pub struct CameraControllerPlugin;
 
 impl Plugin for CameraControllerPlugin {
     fn build(&self, app: &mut App) {
-        app.add_system(camera_controller);
+        app.register_type::<CameraController>()
+            .register_type::<CameraMode>()
+            .register_type::<CameraCollision>()
+            .init_resource::<CameraInputBlock>()
+            .init_resource::<InputContexts>()
+            .add_event::<FrameEntity>()
+            .add_system(
+                camera_controller
+                    .label(CameraSystem::Input),
+            )
+            .add_system(
+                frame_entity.label(CameraSystem::Input),
+            )
+            .add_system(
+                walk_camera
+                    .label(CameraSystem::Walk)
+                    .after(CameraSystem::Input),
+            )
+            .add_system(
+                animate_camera
+                    .label(CameraSystem::Animate)
+                    .after(CameraSystem::Input),
+            )
+            .add_system(
+                collide_camera
+                    .label(CameraSystem::Collide)
+                    .after(CameraSystem::Input)
+                    .after(CameraSystem::Walk)
+                    .after(CameraSystem::Animate),
+            );
     }
 }
@@ -0,0 +1,418 @@
+use crate::{
+    CameraController, CameraInputAccess, CameraInputBlock,
+    CameraSystem, InputContexts,
+};
+use bevy::{prelude::*, time::FixedTimestep};
+use std::f32::consts::TAU;
+
+/// A point the camera passes through, looking at `focus`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraKeyframe {
+    /// Seconds since the start of the path
+    pub time: f32,
+    pub translation: Vec3,
+    pub focus: Vec3,
+}
+
+/// How a [`CameraTrack::Keyframes`] path gets from one keyframe to the next
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PathInterpolation {
+    Linear,
+    /// Smooth curve passing through every keyframe
+    #[default]
+    CatmullRom,
+    /// Single Bézier curve using the keyframes as control points, it
+    /// only passes through the first and last one and ignores the
+    /// keyframe times in between
+    Bezier,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CameraTrack {
+    Keyframes {
+        keyframes: Vec<CameraKeyframe>,
+        interpolation: PathInterpolation,
+    },
+    /// Circles around `focus` and the `up` axis through it once every
+    /// `period` seconds
+    Turntable {
+        focus: Vec3,
+        up: Vec3,
+        radius: f32,
+        /// Angle above the focus, in radians
+        elevation: f32,
+        /// Angle around the up axis at the start, in radians
+        start_angle: f32,
+        period: f32,
+    },
+}
+
+impl CameraTrack {
+    /// Seconds it takes to play the whole track once
+    pub fn duration(&self) -> f32 {
+        match self {
+            CameraTrack::Keyframes {
+                keyframes, ..
+            } => keyframes
+                .last()
+                .map(|keyframe| keyframe.time)
+                .unwrap_or(0.0),
+            CameraTrack::Turntable { period, .. } => {
+                *period
+            }
+        }
+    }
+
+    /// Camera translation and focus `time` seconds into the track
+    pub fn sample(
+        &self,
+        time: f32,
+    ) -> Option<(Vec3, Vec3)> {
+        match self {
+            CameraTrack::Keyframes {
+                keyframes,
+                interpolation,
+            } => sample_keyframes(
+                keyframes,
+                *interpolation,
+                time,
+            ),
+            CameraTrack::Turntable {
+                focus,
+                up,
+                radius,
+                elevation,
+                start_angle,
+                period,
+            } => {
+                let turn = if *period > 0.0 {
+                    time / period
+                } else {
+                    0.0
+                };
+                let rotation = turntable_frame(*up)
+                    * Quat::from_rotation_y(
+                        start_angle + turn * TAU,
+                    )
+                    * Quat::from_rotation_x(-elevation);
+                Some((
+                    *focus + rotation * Vec3::Z * *radius,
+                    *focus,
+                ))
+            }
+        }
+    }
+}
+
+/// Turns the y axis a turntable spins around into `up`
+fn turntable_frame(up: Vec3) -> Quat {
+    Quat::from_rotation_arc(
+        Vec3::Y,
+        up.try_normalize().unwrap_or(Vec3::Y),
+    )
+}
+
+/// Moves the attached camera along a track.
+/// The path advances in fixed steps, so the camera ends up in the same
+/// place after the same number of steps regardless of the frame rate.
+/// The [`CameraController`] ignores input while the path is playing.
+#[derive(Component, Debug, Clone)]
+pub struct CameraPath {
+    pub track: CameraTrack,
+    pub playing: bool,
+    pub looping: bool,
+    /// Advance exactly one step per rendered frame instead of following
+    /// the clock, for capturing the same frames on every run
+    pub recording: bool,
+    /// Seconds into the track
+    pub time: f32,
+    pub key_toggle_play: KeyCode,
+}
+
+impl CameraPath {
+    pub fn new(track: CameraTrack) -> Self {
+        Self {
+            track,
+            playing: true,
+            looping: true,
+            recording: false,
+            time: 0.0,
+            key_toggle_play: KeyCode::P,
+        }
+    }
+
+    /// A turntable around the controller's focus and up vector,
+    /// starting from where the camera is now
+    pub fn turntable(
+        transform: &Transform,
+        controller: &CameraController,
+        period: f32,
+    ) -> Self {
+        let up = controller.up_direction();
+        let offset = turntable_frame(up).inverse()
+            * (transform.translation
+                - controller.orbit_focus);
+        let radius = offset.length();
+        let (elevation, start_angle) = if radius > 0.0 {
+            (
+                (offset.y / radius).clamp(-1.0, 1.0).asin(),
+                offset.x.atan2(offset.z),
+            )
+        } else {
+            (0.0, 0.0)
+        };
+
+        Self::new(CameraTrack::Turntable {
+            focus: controller.orbit_focus,
+            up,
+            radius,
+            elevation,
+            start_angle,
+            period,
+        })
+    }
+
+    pub fn advance(&mut self, step: f32) {
+        if !self.playing {
+            return;
+        }
+
+        let duration = self.track.duration();
+        self.time += step;
+        if self.time >= duration {
+            if self.looping && duration > 0.0 {
+                self.time %= duration;
+            } else {
+                self.time = duration;
+                self.playing = false;
+            }
+        }
+    }
+}
+
+fn sample_keyframes(
+    keyframes: &[CameraKeyframe],
+    interpolation: PathInterpolation,
+    time: f32,
+) -> Option<(Vec3, Vec3)> {
+    let first = keyframes.first()?;
+    let last = keyframes.last()?;
+    if time <= first.time {
+        return Some((first.translation, first.focus));
+    }
+    if time >= last.time {
+        return Some((last.translation, last.focus));
+    }
+
+    if interpolation == PathInterpolation::Bezier {
+        let t =
+            (time - first.time) / (last.time - first.time);
+        let translations: Vec<_> = keyframes
+            .iter()
+            .map(|k| k.translation)
+            .collect();
+        let focuses: Vec<_> =
+            keyframes.iter().map(|k| k.focus).collect();
+        return Some((
+            bezier(&translations, t),
+            bezier(&focuses, t),
+        ));
+    }
+
+    let next = keyframes
+        .iter()
+        .position(|keyframe| keyframe.time > time)?;
+    let current = next - 1;
+    let span =
+        keyframes[next].time - keyframes[current].time;
+    let t = if span > 0.0 {
+        (time - keyframes[current].time) / span
+    } else {
+        1.0
+    };
+
+    if interpolation == PathInterpolation::Linear {
+        let (a, b) =
+            (&keyframes[current], &keyframes[next]);
+        return Some((
+            a.translation.lerp(b.translation, t),
+            a.focus.lerp(b.focus, t),
+        ));
+    }
+
+    let before = &keyframes[current.saturating_sub(1)];
+    let (a, b) = (&keyframes[current], &keyframes[next]);
+    let after =
+        &keyframes[(next + 1).min(keyframes.len() - 1)];
+    Some((
+        catmull_rom(
+            before.translation,
+            a.translation,
+            b.translation,
+            after.translation,
+            t,
+        ),
+        catmull_rom(
+            before.focus,
+            a.focus,
+            b.focus,
+            after.focus,
+            t,
+        ),
+    ))
+}
+
+/// Point `t` of the way from `p1` to `p2` on a uniform Catmull-Rom spline
+fn catmull_rom(
+    p0: Vec3,
+    p1: Vec3,
+    p2: Vec3,
+    p3: Vec3,
+    t: f32,
+) -> Vec3 {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    0.5 * (2.0 * p1
+        + (p2 - p0) * t
+        + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * t2
+        + (3.0 * p1 - p0 - 3.0 * p2 + p3) * t3)
+}
+
+/// De Casteljau's algorithm
+fn bezier(points: &[Vec3], t: f32) -> Vec3 {
+    let mut points = points.to_vec();
+    for len in (1..points.len()).rev() {
+        for i in 0..len {
+            points[i] = points[i].lerp(points[i + 1], t);
+        }
+    }
+    points[0]
+}
+
+fn apply_path(
+    path: &CameraPath,
+    transform: &mut Transform,
+    controller: Option<Mut<CameraController>>,
+) {
+    if let Some((translation, focus)) =
+        path.track.sample(path.time)
+    {
+        let up = controller
+            .as_ref()
+            .map_or(Vec3::Y, |controller| {
+                controller.up_direction()
+            });
+        *transform =
+            Transform::from_translation(translation)
+                .looking_at(focus, up);
+        if let Some(mut controller) = controller {
+            controller.orbit_focus = focus;
+        }
+    }
+}
+
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct CameraPathTimestep(pub f32);
+
+pub fn advance_camera_paths(
+    timestep: Res<CameraPathTimestep>,
+    mut query: Query<(
+        &mut CameraPath,
+        &mut Transform,
+        Option<&mut CameraController>,
+    )>,
+) {
+    for (mut path, mut transform, controller) in
+        query.iter_mut()
+    {
+        // a paused or finished path leaves the camera to the
+        // controller
+        if path.recording || !path.playing {
+            continue;
+        }
+        path.advance(timestep.0);
+        apply_path(&path, &mut transform, controller);
+    }
+}
+
+pub fn record_camera_paths(
+    timestep: Res<CameraPathTimestep>,
+    mut query: Query<(
+        &mut CameraPath,
+        &mut Transform,
+        Option<&mut CameraController>,
+    )>,
+) {
+    for (mut path, mut transform, controller) in
+        query.iter_mut()
+    {
+        if !path.recording || !path.playing {
+            continue;
+        }
+        path.advance(timestep.0);
+        apply_path(&path, &mut transform, controller);
+    }
+}
+
+pub fn toggle_camera_paths(
+    key_input: Res<Input<KeyCode>>,
+    access: CameraInputAccess,
+    mut query: Query<&mut CameraPath>,
+) {
+    if !access.keyboard() {
+        return;
+    }
+    for mut path in query.iter_mut() {
+        if key_input.just_pressed(path.key_toggle_play) {
+            if !path.playing
+                && path.time >= path.track.duration()
+            {
+                // start over once a finished path is played again
+                path.time = 0.0;
+            }
+            path.playing = !path.playing;
+        }
+    }
+}
+
+/// Plays [`CameraPath`]s, advancing them `timestep` seconds at a time
+pub struct CameraPathPlugin {
+    pub timestep: f64,
+}
+
+impl Default for CameraPathPlugin {
+    fn default() -> Self {
+        Self {
+            timestep: 1.0 / 60.0,
+        }
+    }
+}
+
+impl Plugin for CameraPathPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(CameraPathTimestep(
+            self.timestep as f32,
+        ))
+        .init_resource::<CameraInputBlock>()
+        .init_resource::<InputContexts>()
+        .add_system(
+            toggle_camera_paths.label(CameraSystem::Input),
+        )
+        .add_system_set(
+            SystemSet::new()
+                .label(CameraSystem::Paths)
+                .after(CameraSystem::Input)
+                .before(CameraSystem::Collide)
+                .with_run_criteria(FixedTimestep::step(
+                    self.timestep,
+                ))
+                .with_system(advance_camera_paths),
+        )
+        .add_system(
+            record_camera_paths
+                .label(CameraSystem::Paths)
+                .after(CameraSystem::Input)
+                .before(CameraSystem::Collide),
+        );
+    }
+}
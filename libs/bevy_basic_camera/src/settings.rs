@@ -0,0 +1,344 @@
+use crate::{
+    CameraController, CameraInputAccess, CameraInputBlock,
+    CameraSystem, InputContexts,
+};
+use bevy::{asset::FileAssetIo, prelude::*};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// The personal part of a [`CameraController`], its sensitivity, speeds
+/// and bindings. Fields missing from the file keep their defaults.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Serialize, Deserialize,
+)]
+#[serde(default)]
+pub struct CameraSettings {
+    pub sensitivity: f32,
+    pub walk_speed: f32,
+    pub run_speed: f32,
+    pub friction: f32,
+    pub scroll_wheel_speed: f32,
+    pub scroll_pixels_per_line: f32,
+    pub keyboard_zoom_speed: f32,
+    pub keyboard_orbit_speed: f32,
+    pub roll_speed: f32,
+    pub transition_time: f32,
+    pub key_forward: KeyCode,
+    pub key_back: KeyCode,
+    pub key_left: KeyCode,
+    pub key_right: KeyCode,
+    pub key_up: KeyCode,
+    pub key_down: KeyCode,
+    pub key_run: KeyCode,
+    pub key_cycle_mode: KeyCode,
+    pub key_orbit_left: KeyCode,
+    pub key_orbit_right: KeyCode,
+    pub key_orbit_up: KeyCode,
+    pub key_orbit_down: KeyCode,
+    pub key_zoom_in: KeyCode,
+    pub key_zoom_out: KeyCode,
+    pub key_toggle_projection: KeyCode,
+    pub keyboard_key_enable_mouse: KeyCode,
+    pub key_jump: KeyCode,
+    pub key_roll_left: KeyCode,
+    pub key_roll_right: KeyCode,
+    pub mouse_key_enable_mouse: MouseButton,
+    pub mouse_key_pan: MouseButton,
+}
+
+impl Default for CameraSettings {
+    fn default() -> Self {
+        Self::from_controller(&CameraController::default())
+    }
+}
+
+impl CameraSettings {
+    pub fn from_controller(
+        controller: &CameraController,
+    ) -> Self {
+        // every field by name, so one added to the controller
+        // doesn't build until it's sorted in here
+        let CameraController {
+            sensitivity,
+            walk_speed,
+            run_speed,
+            friction,
+            scroll_wheel_speed,
+            scroll_pixels_per_line,
+            keyboard_zoom_speed,
+            keyboard_orbit_speed,
+            roll_speed,
+            transition_time,
+            key_forward,
+            key_back,
+            key_left,
+            key_right,
+            key_up,
+            key_down,
+            key_run,
+            key_cycle_mode,
+            key_orbit_left,
+            key_orbit_right,
+            key_orbit_up,
+            key_orbit_down,
+            key_zoom_in,
+            key_zoom_out,
+            key_toggle_projection,
+            keyboard_key_enable_mouse,
+            key_jump,
+            key_roll_left,
+            key_roll_right,
+            mouse_key_enable_mouse,
+            mouse_key_pan,
+            enabled: _,
+            initialized: _,
+            up: _,
+            six_dof: _,
+            eye_height: _,
+            gravity: _,
+            jump_speed: _,
+            vertical_speed: _,
+            grounded: _,
+            velocity: _,
+            orbit_focus: _,
+            mode: _,
+            min_radius: _,
+            max_radius: _,
+            min_scale: _,
+            max_scale: _,
+            perspective_fov: _,
+            frame_duration: _,
+            transition_progress: _,
+            transition_from: _,
+            last_mode: _,
+            upside_down: _,
+        } = controller;
+        Self {
+            sensitivity: *sensitivity,
+            walk_speed: *walk_speed,
+            run_speed: *run_speed,
+            friction: *friction,
+            scroll_wheel_speed: *scroll_wheel_speed,
+            scroll_pixels_per_line: *scroll_pixels_per_line,
+            keyboard_zoom_speed: *keyboard_zoom_speed,
+            keyboard_orbit_speed: *keyboard_orbit_speed,
+            roll_speed: *roll_speed,
+            transition_time: *transition_time,
+            key_forward: *key_forward,
+            key_back: *key_back,
+            key_left: *key_left,
+            key_right: *key_right,
+            key_up: *key_up,
+            key_down: *key_down,
+            key_run: *key_run,
+            key_cycle_mode: *key_cycle_mode,
+            key_orbit_left: *key_orbit_left,
+            key_orbit_right: *key_orbit_right,
+            key_orbit_up: *key_orbit_up,
+            key_orbit_down: *key_orbit_down,
+            key_zoom_in: *key_zoom_in,
+            key_zoom_out: *key_zoom_out,
+            key_toggle_projection: *key_toggle_projection,
+            keyboard_key_enable_mouse:
+                *keyboard_key_enable_mouse,
+            key_jump: *key_jump,
+            key_roll_left: *key_roll_left,
+            key_roll_right: *key_roll_right,
+            mouse_key_enable_mouse: *mouse_key_enable_mouse,
+            mouse_key_pan: *mouse_key_pan,
+        }
+    }
+
+    pub fn apply(&self, controller: &mut CameraController) {
+        // a field left unapplied is an unused variable
+        let CameraSettings {
+            sensitivity,
+            walk_speed,
+            run_speed,
+            friction,
+            scroll_wheel_speed,
+            scroll_pixels_per_line,
+            keyboard_zoom_speed,
+            keyboard_orbit_speed,
+            roll_speed,
+            transition_time,
+            key_forward,
+            key_back,
+            key_left,
+            key_right,
+            key_up,
+            key_down,
+            key_run,
+            key_cycle_mode,
+            key_orbit_left,
+            key_orbit_right,
+            key_orbit_up,
+            key_orbit_down,
+            key_zoom_in,
+            key_zoom_out,
+            key_toggle_projection,
+            keyboard_key_enable_mouse,
+            key_jump,
+            key_roll_left,
+            key_roll_right,
+            mouse_key_enable_mouse,
+            mouse_key_pan,
+        } = *self;
+        controller.sensitivity = sensitivity;
+        controller.walk_speed = walk_speed;
+        controller.run_speed = run_speed;
+        controller.friction = friction;
+        controller.scroll_wheel_speed = scroll_wheel_speed;
+        controller.scroll_pixels_per_line =
+            scroll_pixels_per_line;
+        controller.keyboard_zoom_speed =
+            keyboard_zoom_speed;
+        controller.keyboard_orbit_speed =
+            keyboard_orbit_speed;
+        controller.roll_speed = roll_speed;
+        controller.transition_time = transition_time;
+        controller.key_forward = key_forward;
+        controller.key_back = key_back;
+        controller.key_left = key_left;
+        controller.key_right = key_right;
+        controller.key_up = key_up;
+        controller.key_down = key_down;
+        controller.key_run = key_run;
+        controller.key_cycle_mode = key_cycle_mode;
+        controller.key_orbit_left = key_orbit_left;
+        controller.key_orbit_right = key_orbit_right;
+        controller.key_orbit_up = key_orbit_up;
+        controller.key_orbit_down = key_orbit_down;
+        controller.key_zoom_in = key_zoom_in;
+        controller.key_zoom_out = key_zoom_out;
+        controller.key_toggle_projection =
+            key_toggle_projection;
+        controller.keyboard_key_enable_mouse =
+            keyboard_key_enable_mouse;
+        controller.key_jump = key_jump;
+        controller.key_roll_left = key_roll_left;
+        controller.key_roll_right = key_roll_right;
+        controller.mouse_key_enable_mouse =
+            mouse_key_enable_mouse;
+        controller.mouse_key_pan = mouse_key_pan;
+    }
+}
+
+/// [`CameraSettings`] loaded from and saved to `path`, applied to every
+/// [`CameraController`] as it's added
+#[derive(Resource, Debug, Clone)]
+pub struct CameraSettingsFile {
+    /// `None` until the file was read, or when there is none
+    pub settings: Option<CameraSettings>,
+    pub path: PathBuf,
+    /// Saves the settings of the current camera, e.g. after tweaking
+    /// them in an inspector
+    pub key_save: KeyCode,
+}
+
+impl Default for CameraSettingsFile {
+    fn default() -> Self {
+        Self {
+            settings: None,
+            path: FileAssetIo::get_base_path()
+                .join("camera_settings.ron"),
+            key_save: KeyCode::F5,
+        }
+    }
+}
+
+impl CameraSettingsFile {
+    pub fn load(&mut self) {
+        let contents =
+            match std::fs::read_to_string(&self.path) {
+                Ok(contents) => contents,
+                // stick to the defaults
+                Err(_) => return,
+            };
+        match ron::from_str::<CameraSettings>(&contents) {
+            Ok(settings) => self.settings = Some(settings),
+            Err(err) => warn!(
+                "Failed to read camera settings from {:?}: {}",
+                self.path, err
+            ),
+        }
+    }
+
+    pub fn save(&mut self, settings: CameraSettings) {
+        self.settings = Some(settings);
+        let result = ron::ser::to_string_pretty(
+            &settings,
+            ron::ser::PrettyConfig::default(),
+        )
+        .map_err(|err| err.to_string())
+        .and_then(|contents| {
+            std::fs::write(&self.path, contents)
+                .map_err(|err| err.to_string())
+        });
+        match result {
+            Ok(()) => info!("Saved camera settings to {:?}", self.path),
+            Err(err) => warn!(
+                "Failed to save camera settings to {:?}: {}",
+                self.path, err
+            ),
+        }
+    }
+}
+
+fn load_camera_settings(
+    mut file: ResMut<CameraSettingsFile>,
+) {
+    file.load();
+}
+
+pub fn apply_camera_settings(
+    file: Res<CameraSettingsFile>,
+    mut query: Query<
+        &mut CameraController,
+        Added<CameraController>,
+    >,
+) {
+    if let Some(settings) = &file.settings {
+        for mut controller in query.iter_mut() {
+            settings.apply(&mut controller);
+        }
+    }
+}
+
+pub fn save_camera_settings(
+    key_input: Res<Input<KeyCode>>,
+    access: CameraInputAccess,
+    mut file: ResMut<CameraSettingsFile>,
+    query: Query<&CameraController>,
+) {
+    if !access.keyboard()
+        || !key_input.just_pressed(file.key_save)
+    {
+        return;
+    }
+    if let Some(controller) = query.iter().next() {
+        file.save(CameraSettings::from_controller(
+            controller,
+        ));
+    }
+}
+
+/// Applies `camera_settings.ron` next to the executable (or the
+/// `CARGO_MANIFEST_DIR`) to every camera, `F5` saves the current ones.
+/// Keep the file out of version control so everyone can have their own.
+#[derive(Default)]
+pub struct CameraSettingsPlugin;
+
+impl Plugin for CameraSettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CameraSettingsFile>()
+            .add_startup_system(load_camera_settings)
+            .init_resource::<CameraInputBlock>()
+            .init_resource::<InputContexts>()
+            .add_system(
+                apply_camera_settings
+                    .before(CameraSystem::Input),
+            )
+            .add_system(save_camera_settings);
+    }
+}
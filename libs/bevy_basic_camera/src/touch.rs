@@ -0,0 +1,141 @@
+use bevy::{
+    input::touch::{TouchInput, TouchPhase},
+    prelude::*,
+    utils::HashMap,
+};
+
+/// What the fingers on a touch screen did over a frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TouchGesture {
+    /// One finger drag, in logical pixels
+    pub drag: Vec2,
+    /// How far the middle of two fingers moved, in logical pixels
+    pub pan: Vec2,
+    /// The distance between two fingers relative to the frame before,
+    /// above `1.0` when spreading them
+    pub pinch: f32,
+    /// Radians two fingers turned, clockwise on screen
+    pub rotate: f32,
+}
+
+impl Default for TouchGesture {
+    fn default() -> Self {
+        Self {
+            drag: Vec2::ZERO,
+            pan: Vec2::ZERO,
+            pinch: 1.0,
+            rotate: 0.0,
+        }
+    }
+}
+
+impl TouchGesture {
+    /// The gesture made by fingers moving from the first to the second
+    /// position of each pair. Anything but one or two fingers is no
+    /// gesture.
+    pub fn from_positions(
+        fingers: &[(Vec2, Vec2)],
+    ) -> Self {
+        match *fingers {
+            [(previous, current)] => Self {
+                drag: current - previous,
+                ..default()
+            },
+            [(previous_a, current_a), (previous_b, current_b)] =>
+            {
+                let previous_span = previous_b - previous_a;
+                let current_span = current_b - current_a;
+                let pinch = if previous_span.length() > 0.0
+                {
+                    current_span.length()
+                        / previous_span.length()
+                } else {
+                    1.0
+                };
+                Self {
+                    pan: (current_a + current_b
+                        - previous_a
+                        - previous_b)
+                        * 0.5,
+                    pinch,
+                    rotate: previous_span
+                        .perp_dot(current_span)
+                        .atan2(
+                            previous_span.dot(current_span),
+                        ),
+                    ..default()
+                }
+            }
+            _ => Self::default(),
+        }
+    }
+
+    /// The pinch as scroll steps of a controller with
+    /// `scroll_wheel_speed`, so it zooms like the scroll wheel
+    pub fn zoom_steps(
+        &self,
+        scroll_wheel_speed: f32,
+    ) -> f32 {
+        if self.pinch > 0.0 && scroll_wheel_speed > 0.0 {
+            (1.0 - 1.0 / self.pinch) / scroll_wheel_speed
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Fingers on the screen, followed through [`TouchInput`] events.
+/// Unlike [`Touches`] this knows where every finger was at the start of
+/// the frame, even when several moves arrive in one frame or none at all.
+#[derive(Debug, Default, Clone)]
+pub struct TouchTracker {
+    positions: HashMap<u64, Vec2>,
+}
+
+impl TouchTracker {
+    /// Applies a frame's worth of events and returns the gesture the
+    /// fingers made. Nothing happens on the frame a finger lands or
+    /// lifts, so the camera doesn't jump.
+    pub fn update<'a>(
+        &mut self,
+        events: impl IntoIterator<Item = &'a TouchInput>,
+    ) -> TouchGesture {
+        let previous = self.positions.clone();
+        for event in events {
+            match event.phase {
+                TouchPhase::Started | TouchPhase::Moved => {
+                    self.positions
+                        .insert(event.id, event.position);
+                }
+                TouchPhase::Ended
+                | TouchPhase::Cancelled => {
+                    self.positions.remove(&event.id);
+                }
+            }
+        }
+
+        let mut fingers: Vec<_> = self
+            .positions
+            .iter()
+            .filter_map(|(id, current)| {
+                previous.get(id).map(|previous| {
+                    (*id, *previous, *current)
+                })
+            })
+            .collect();
+        if fingers.len() != previous.len()
+            || fingers.len() != self.positions.len()
+        {
+            return TouchGesture::default();
+        }
+        // keep the fingers in the same order from frame to frame
+        fingers.sort_by_key(|(id, ..)| *id);
+        let fingers: Vec<_> = fingers
+            .into_iter()
+            .map(|(_, previous, current)| {
+                (previous, current)
+            })
+            .collect();
+        TouchGesture::from_positions(&fingers)
+    }
+}
@@ -0,0 +1,151 @@
+use crate::{
+    CameraController, CameraInputAccess, CameraMode,
+    CameraPath, CameraTween,
+};
+use bevy::prelude::*;
+use std::sync::Arc;
+
+/// Surface height above `radius`, given the direction from the centre
+pub type SurfaceHeight =
+    Arc<dyn Fn(Vec3) -> f32 + Send + Sync>;
+
+/// A planet cameras in [`CameraMode::Walk`] stand on, centred on the
+/// entity's translation
+/// ```ignore
+///  .insert(WalkSurface::new(10.0).with_height(|direction| {
+///      0.1 * (direction.x * 20.0).sin()
+///  }))
+/// ```
+#[derive(Component, Clone)]
+pub struct WalkSurface {
+    /// Distance from the centre to the surface, in world units
+    pub radius: f32,
+    /// Displacement of the surface, for terrain that isn't a perfect
+    /// sphere
+    pub height: Option<SurfaceHeight>,
+}
+
+impl WalkSurface {
+    pub fn new(radius: f32) -> Self {
+        Self {
+            radius,
+            height: None,
+        }
+    }
+
+    pub fn with_height(
+        mut self,
+        height: impl Fn(Vec3) -> f32 + Send + Sync + 'static,
+    ) -> Self {
+        self.height = Some(Arc::new(height));
+        self
+    }
+
+    /// Distance from the centre to the ground in `direction`, which has
+    /// to be normalized
+    pub fn ground_distance(&self, direction: Vec3) -> f32 {
+        self.radius
+            + self
+                .height
+                .as_ref()
+                .map_or(0.0, |height| height(direction))
+    }
+}
+
+/// Keeps walking cameras upright on the nearest [`WalkSurface`],
+/// pulls them down to it and lets them jump off it
+#[allow(clippy::type_complexity)]
+pub fn walk_camera(
+    time: Res<Time>,
+    key_input: Res<Input<KeyCode>>,
+    access: CameraInputAccess,
+    surfaces: Query<(&WalkSurface, &GlobalTransform)>,
+    mut query: Query<(
+        &mut Transform,
+        &mut CameraController,
+        Option<&CameraTween>,
+        Option<&CameraPath>,
+    )>,
+) {
+    let dt = time.delta_seconds();
+
+    for (mut transform, mut controller, tween, path) in
+        query.iter_mut()
+    {
+        let path_playing =
+            matches!(path, Some(path) if path.playing);
+        if !controller.enabled
+            || controller.mode != CameraMode::Walk
+            || tween.is_some()
+            || path_playing
+        {
+            continue;
+        }
+
+        // the surface whose ground is the closest
+        let nearest = surfaces
+            .iter()
+            .filter_map(|(surface, global_transform)| {
+                let center = global_transform.translation();
+                let offset = transform.translation - center;
+                let direction = offset.try_normalize()?;
+                let ground =
+                    surface.ground_distance(direction);
+                Some((
+                    center,
+                    direction,
+                    offset.length() - ground,
+                ))
+            })
+            .min_by(|a, b| a.2.total_cmp(&b.2));
+        let (center, up, altitude) = match nearest {
+            Some(nearest) => nearest,
+            None => {
+                controller.grounded = false;
+                continue;
+            }
+        };
+
+        // carry the view along as the up vector turns underneath it
+        let old_up = controller.up_direction();
+        transform.rotation =
+            Quat::from_rotation_arc(old_up, up)
+                * transform.rotation;
+        controller.up = up;
+        if !controller.six_dof {
+            controller.level(&mut transform);
+        }
+
+        if controller.grounded
+            && access.keyboard()
+            && key_input.just_pressed(controller.key_jump)
+        {
+            controller.vertical_speed =
+                controller.jump_speed;
+            controller.grounded = false;
+        }
+        if !controller.grounded {
+            controller.vertical_speed -=
+                controller.gravity * dt;
+        }
+
+        let height =
+            altitude + controller.vertical_speed * dt;
+        let height = if height <= controller.eye_height {
+            controller.vertical_speed = 0.0;
+            controller.grounded = true;
+            controller.eye_height
+        } else {
+            // walking off a slope leaves the ground
+            controller.grounded = false;
+            height
+        };
+        let ground = (transform.translation - center)
+            .length()
+            - altitude;
+        let translation = center + up * (ground + height);
+        let offset = translation - transform.translation;
+        transform.translation = translation;
+        controller.orbit_focus += offset;
+    }
+}
@@ -0,0 +1,499 @@
+mod common;
+
+use bevy::{
+    prelude::*, render::camera::CameraProjection,
+    time::FixedTimesteps,
+};
+use bevy_basic_camera::{
+    CameraBookmark, CameraCollision, CameraController,
+    CameraInputBlock, CameraMode, CameraPath,
+    CameraPathPlugin, InputContexts, WalkSurface,
+};
+use common::*;
+use std::f32::consts::PI;
+
+fn orbit_camera() -> CameraHarness {
+    CameraHarness::new(
+        Transform::from_xyz(0.0, 0.0, 5.0)
+            .looking_at(Vec3::ZERO, Vec3::Y),
+        CameraController {
+            mode: CameraMode::Orbit,
+            ..default()
+        },
+    )
+}
+
+#[test]
+fn flying_forward_moves_along_the_view() {
+    let mut harness = CameraHarness::new(
+        Transform::default(),
+        CameraController::default(),
+    );
+    harness.press(KeyCode::W);
+    harness.step(60);
+
+    let walk_speed = harness.controller().walk_speed;
+    assert_vec3_eq(
+        harness.transform().translation,
+        Vec3::new(0.0, 0.0, -walk_speed),
+    );
+}
+
+#[test]
+fn friction_stops_the_camera() {
+    let mut harness = CameraHarness::new(
+        Transform::default(),
+        CameraController::default(),
+    );
+    harness.press(KeyCode::W);
+    harness.step(1);
+    harness.release(KeyCode::W);
+    harness.step(60);
+
+    assert_eq!(harness.controller().velocity, Vec3::ZERO);
+    let stopped_at = harness.transform().translation;
+    harness.step(10);
+    assert_eq!(harness.transform().translation, stopped_at);
+}
+
+#[test]
+fn looking_stops_short_of_the_up_vector() {
+    let mut harness = CameraHarness::new(
+        Transform::default(),
+        CameraController::default(),
+    );
+    harness.press_mouse(MouseButton::Left);
+    harness.move_mouse(Vec2::new(0.0, -1e5));
+    harness.step(1);
+
+    let transform = harness.transform();
+    let tilt = transform.forward().dot(Vec3::Y);
+    assert!(tilt > 0.99 && tilt < 1.0, "tilt was {tilt}");
+    assert!(transform.up().y > 0.0);
+}
+
+#[test]
+fn tab_cycles_through_modes() {
+    let mut harness = CameraHarness::new(
+        Transform::default(),
+        CameraController::default(),
+    );
+    harness.press(KeyCode::Tab);
+    harness.step(1);
+    assert_eq!(
+        harness.controller().mode,
+        CameraMode::Orbit
+    );
+
+    // held keys only count once
+    harness.step(1);
+    assert_eq!(
+        harness.controller().mode,
+        CameraMode::Orbit
+    );
+}
+
+#[test]
+fn orbit_drag_turns_around_the_focus() {
+    let mut harness = orbit_camera();
+    harness.press_mouse(MouseButton::Left);
+    // a quarter of the window width is a quarter turn
+    harness.move_mouse(Vec2::new(WINDOW_SIZE.x / 4.0, 0.0));
+    harness.step(1);
+
+    let transform = harness.transform();
+    assert_vec3_eq(
+        transform.translation,
+        Vec3::new(-5.0, 0.0, 0.0),
+    );
+    assert_vec3_eq(transform.forward(), Vec3::X);
+
+    harness.release_mouse(MouseButton::Left);
+    harness.move_mouse(Vec2::new(100.0, 100.0));
+    harness.step(1);
+    assert_eq!(harness.transform(), transform);
+}
+
+#[test]
+fn orbit_keys_keep_the_radius() {
+    let mut harness = orbit_camera();
+    harness.press(KeyCode::Left);
+    harness.press(KeyCode::Up);
+    harness.step(30);
+
+    let transform = harness.transform();
+    assert_f32_eq(transform.translation.length(), 5.0);
+    assert_vec3_eq(
+        transform.forward(),
+        -transform.translation.normalize(),
+    );
+}
+
+#[test]
+fn scrolling_zooms_towards_the_focus() {
+    let mut harness = orbit_camera();
+    harness.scroll(1.0);
+    harness.step(1);
+
+    let speed = harness.controller().scroll_wheel_speed;
+    assert_vec3_eq(
+        harness.transform().translation,
+        Vec3::new(0.0, 0.0, 5.0 * (1.0 - speed)),
+    );
+}
+
+#[test]
+fn zoom_is_clamped() {
+    let mut harness = orbit_camera();
+    harness
+        .app
+        .world
+        .get_mut::<CameraController>(harness.camera)
+        .unwrap()
+        .max_radius = 10.0;
+    harness.scroll(-100.0);
+    harness.step(1);
+
+    assert_f32_eq(
+        harness.transform().translation.length(),
+        10.0,
+    );
+}
+
+#[test]
+fn pan_drag_moves_the_focus_with_the_cursor() {
+    let mut harness = orbit_camera();
+    harness.press_mouse(MouseButton::Middle);
+    harness
+        .move_mouse(Vec2::new(0.0, WINDOW_SIZE.y / 10.0));
+    harness.step(1);
+
+    // a tenth of the view height at the focus, for the default fov
+    let offset = 2.0 * 5.0 * (PI / 8.0).tan() / 10.0;
+    assert_vec3_eq(
+        harness.controller().orbit_focus,
+        Vec3::new(0.0, offset, 0.0),
+    );
+    assert_vec3_eq(
+        harness.transform().translation,
+        Vec3::new(0.0, offset, 5.0),
+    );
+}
+
+#[test]
+fn dragging_without_a_window_is_ignored() {
+    let start = Transform::from_xyz(0.0, 0.0, 5.0)
+        .looking_at(Vec3::ZERO, Vec3::Y);
+    let mut harness = CameraHarness::headless(
+        start,
+        CameraController {
+            mode: CameraMode::Orbit,
+            ..default()
+        },
+    );
+    harness.press_mouse(MouseButton::Left);
+    harness.move_mouse(Vec2::new(100.0, 100.0));
+    harness.step(1);
+    assert_eq!(harness.transform(), start);
+
+    // keys don't need a window
+    harness.press(KeyCode::W);
+    harness.step(1);
+    assert_ne!(harness.transform(), start);
+}
+
+#[test]
+fn flying_without_a_window_still_looks_around() {
+    let mut harness = CameraHarness::headless(
+        Transform::default(),
+        CameraController::default(),
+    );
+    harness.press_mouse(MouseButton::Left);
+    harness.move_mouse(Vec2::new(100.0, 0.0));
+    harness.step(1);
+    assert_ne!(
+        harness.transform().rotation,
+        Quat::IDENTITY
+    );
+
+    harness.release_mouse(MouseButton::Left);
+    harness.press(KeyCode::W);
+    harness.step(10);
+    assert_ne!(harness.transform().translation, Vec3::ZERO);
+}
+
+#[test]
+fn walking_lands_on_the_surface() {
+    let mut harness = CameraHarness::new(
+        Transform::from_xyz(0.0, 20.0, 0.0),
+        CameraController {
+            mode: CameraMode::Walk,
+            ..default()
+        },
+    );
+    harness.app.world.spawn((
+        WalkSurface::new(10.0),
+        GlobalTransform::default(),
+    ));
+    harness.step(300);
+
+    let eye_height = harness.controller().eye_height;
+    assert!(harness.controller().grounded);
+    assert_vec3_eq(
+        harness.transform().translation,
+        Vec3::new(0.0, 10.0 + eye_height, 0.0),
+    );
+
+    harness.press(KeyCode::Space);
+    harness.step(1);
+    assert!(!harness.controller().grounded);
+    assert!(
+        harness.transform().translation.y
+            > 10.0 + eye_height
+    );
+}
+
+#[test]
+fn walking_follows_the_curve_of_the_surface() {
+    let mut harness = CameraHarness::new(
+        Transform::from_xyz(0.0, 11.0, 0.0),
+        CameraController {
+            mode: CameraMode::Walk,
+            ..default()
+        },
+    );
+    harness.app.world.spawn((
+        WalkSurface::new(10.0),
+        GlobalTransform::default(),
+    ));
+    harness.step(60);
+    harness.press(KeyCode::W);
+    harness.step(60);
+
+    let transform = harness.transform();
+    let up = transform.translation.normalize();
+    let eye_height = harness.controller().eye_height;
+    assert_f32_eq(
+        transform.translation.length(),
+        10.0 + eye_height,
+    );
+    assert!(up.z < -0.1, "didn't walk forward: {up}");
+    // still level with the ground underneath
+    assert_f32_eq(transform.forward().dot(up), 0.0);
+}
+
+#[test]
+fn collision_keeps_the_camera_out_of_surfaces() {
+    let mut harness = orbit_camera();
+    harness.app.world.spawn((
+        WalkSurface::new(2.0),
+        GlobalTransform::default(),
+    ));
+    harness.app.world.entity_mut(harness.camera).insert(
+        CameraCollision {
+            min_surface_distance: 0.5,
+            ..default()
+        },
+    );
+    harness.scroll(9.0);
+    harness.step(1);
+
+    assert_f32_eq(
+        harness.transform().translation.length(),
+        2.5,
+    );
+}
+
+#[test]
+fn collision_stops_in_front_of_hills() {
+    // looking across the top of a planet with a ridge along x = 0
+    let focus = Vec3::new(-3.0, 10.5, 0.0);
+    let mut harness = CameraHarness::new(
+        Transform::from_xyz(3.0, 10.5, 0.0)
+            .looking_at(focus, Vec3::Y),
+        CameraController {
+            mode: CameraMode::Orbit,
+            orbit_focus: focus,
+            ..default()
+        },
+    );
+    harness.app.world.spawn((
+        WalkSurface::new(10.0).with_height(|direction| {
+            2.0 * (1.0 - direction.x.abs() * 10.0).max(0.0)
+        }),
+        GlobalTransform::default(),
+    ));
+    harness
+        .app
+        .world
+        .entity_mut(harness.camera)
+        .insert(CameraCollision::default());
+    harness.step(1);
+
+    let translation = harness.transform().translation;
+    assert!(
+        translation.x < 0.0,
+        "went through the ridge to {translation}"
+    );
+    assert_f32_eq(translation.y, 10.5);
+}
+
+#[test]
+fn blocked_input_is_ignored() {
+    let mut harness = orbit_camera();
+    let start = harness.transform();
+    harness.app.insert_resource(CameraInputBlock {
+        pointer: true,
+        keyboard: true,
+    });
+    harness.press(KeyCode::W);
+    harness.press_mouse(MouseButton::Left);
+    harness.move_mouse(Vec2::new(100.0, 100.0));
+    harness.scroll(1.0);
+    harness.step(1);
+    assert_eq!(harness.transform(), start);
+
+    // the blocked motion doesn't show up once unblocked
+    harness
+        .app
+        .insert_resource(CameraInputBlock::default());
+    harness.release(KeyCode::W);
+    harness.step(1);
+    assert_eq!(harness.transform(), start);
+}
+
+#[test]
+fn only_the_top_input_context_receives_input() {
+    let mut harness = orbit_camera();
+    let start = harness.transform();
+    let mut contexts = InputContexts::default();
+    contexts.push(InputContexts::CAMERA);
+    contexts.push("object-manipulation");
+    harness.app.insert_resource(contexts);
+    harness.press(KeyCode::Left);
+    harness.step(5);
+    assert_eq!(harness.transform(), start);
+
+    harness
+        .app
+        .world
+        .resource_mut::<InputContexts>()
+        .remove("object-manipulation");
+    harness.step(5);
+    assert_ne!(harness.transform(), start);
+}
+
+#[test]
+fn bookmarks_recall_the_orbit_focus() {
+    // looking away from the focus, like after flying around
+    let transform = Transform::from_xyz(0.0, 0.0, 5.0)
+        .looking_at(Vec3::new(3.0, 0.0, 0.0), Vec3::Y);
+    let controller = CameraController {
+        orbit_focus: Vec3::new(0.0, 1.0, 0.0),
+        ..default()
+    };
+    let pose = CameraBookmark::from_camera(
+        &transform,
+        &controller,
+        None,
+    )
+    .pose();
+
+    assert_vec3_eq(pose.focus, controller.orbit_focus);
+    assert_vec3_eq(
+        pose.translation(),
+        transform.translation,
+    );
+    assert_eq!(pose.rotation, transform.rotation);
+}
+
+#[test]
+fn orthographic_bookmarks_keep_their_framing() {
+    let mut transform = Transform::from_xyz(0.0, 0.0, 5.0)
+        .looking_at(Vec3::ZERO, Vec3::Y);
+    let mut controller = CameraController::default();
+    let mut projection = Projection::Perspective(default());
+    controller
+        .toggle_projection(&mut transform, &mut projection);
+    let mut saved = projection.clone();
+    let bookmark = CameraBookmark::from_camera(
+        &transform,
+        &controller,
+        Some(&projection),
+    );
+
+    controller
+        .toggle_projection(&mut transform, &mut projection);
+    let mut recalled = bookmark
+        .projection
+        .unwrap()
+        .to_projection(&projection);
+
+    saved.update(WINDOW_SIZE.x, WINDOW_SIZE.y);
+    recalled.update(WINDOW_SIZE.x, WINDOW_SIZE.y);
+    assert_eq!(
+        recalled.get_projection_matrix(),
+        saved.get_projection_matrix(),
+    );
+}
+
+#[test]
+fn turntables_spin_around_the_up_vector() {
+    // standing on the side of a planet, up is along x
+    let up = Vec3::X;
+    let transform = Transform::from_xyz(3.0, 0.0, 4.0)
+        .looking_at(Vec3::ZERO, up);
+    let controller = CameraController { up, ..default() };
+    let path =
+        CameraPath::turntable(&transform, &controller, 4.0);
+
+    let (start, focus) = path.track.sample(0.0).unwrap();
+    assert_vec3_eq(start, transform.translation);
+    assert_vec3_eq(focus, Vec3::ZERO);
+    for time in [0.5, 1.0, 2.5] {
+        let (translation, _) =
+            path.track.sample(time).unwrap();
+        // level around the up axis, at the same distance
+        assert_f32_eq(translation.dot(up), 3.0);
+        assert_f32_eq(translation.length(), 5.0);
+    }
+    let (halfway, _) = path.track.sample(2.0).unwrap();
+    assert_vec3_eq(halfway, Vec3::new(3.0, 0.0, -4.0));
+}
+
+#[test]
+fn paused_paths_leave_the_camera_to_the_controller() {
+    let mut harness = CameraHarness::new(
+        Transform::default(),
+        CameraController::default(),
+    );
+    // the harness drives time by hand, without TimePlugin
+    harness
+        .app
+        .init_resource::<FixedTimesteps>()
+        .add_plugin(CameraPathPlugin::default());
+    let path = CameraPath {
+        playing: false,
+        ..CameraPath::turntable(
+            &harness.transform(),
+            &CameraController {
+                orbit_focus: Vec3::new(0.0, 0.0, -5.0),
+                ..default()
+            },
+            4.0,
+        )
+    };
+    harness
+        .app
+        .world
+        .entity_mut(harness.camera)
+        .insert(path);
+    harness.press(KeyCode::W);
+    harness.step(60);
+
+    let walk_speed = harness.controller().walk_speed;
+    assert_vec3_eq(
+        harness.transform().translation,
+        Vec3::new(0.0, 0.0, -walk_speed),
+    );
+}
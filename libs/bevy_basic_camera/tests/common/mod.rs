@@ -0,0 +1,212 @@
+// every test binary only uses part of the harness
+#![allow(dead_code)]
+
+use bevy::{
+    input::{
+        mouse::{MouseMotion, MouseScrollUnit, MouseWheel},
+        touch::{TouchInput, TouchPhase},
+    },
+    prelude::*,
+    time::TimePlugin,
+    utils::{Duration, Instant},
+    window::WindowId,
+};
+use bevy_basic_camera::{
+    CameraController, CameraControllerPlugin,
+};
+
+/// Seconds every [`CameraHarness::step`] frame takes
+pub const FRAME: f32 = 1.0 / 60.0;
+
+/// Width and height of the fake primary window
+pub const WINDOW_SIZE: Vec2 = Vec2::new(800.0, 600.0);
+
+/// An app running the camera systems without a window or GPU. Input is
+/// fed in by hand and time advances exactly [`FRAME`] per step.
+pub struct CameraHarness {
+    pub app: App,
+    pub camera: Entity,
+}
+
+impl CameraHarness {
+    /// A camera rendering to a primary window of [`WINDOW_SIZE`]
+    pub fn new(
+        transform: Transform,
+        controller: CameraController,
+    ) -> Self {
+        let mut harness =
+            Self::headless(transform, controller);
+        let descriptor = WindowDescriptor {
+            width: WINDOW_SIZE.x,
+            height: WINDOW_SIZE.y,
+            ..default()
+        };
+        let mut windows = Windows::default();
+        windows.add(Window::new(
+            WindowId::primary(),
+            &descriptor,
+            WINDOW_SIZE.x as u32,
+            WINDOW_SIZE.y as u32,
+            1.0,
+            None,
+            None,
+        ));
+        harness.app.insert_resource(windows);
+        harness
+    }
+
+    /// A camera whose window doesn't exist
+    pub fn headless(
+        transform: Transform,
+        controller: CameraController,
+    ) -> Self {
+        let mut app = App::new();
+        app.add_plugins(
+            MinimalPlugins.build().disable::<TimePlugin>(),
+        )
+        .init_resource::<Input<KeyCode>>()
+        .init_resource::<Input<MouseButton>>()
+        .add_event::<MouseMotion>()
+        .add_event::<MouseWheel>()
+        .add_event::<TouchInput>()
+        .add_plugin(CameraControllerPlugin);
+
+        let mut time = Time::default();
+        time.update_with_instant(Instant::now());
+        app.insert_resource(time);
+
+        let camera = app
+            .world
+            .spawn((
+                Camera3dBundle {
+                    transform,
+                    ..default()
+                },
+                controller,
+            ))
+            .id();
+
+        Self { app, camera }
+    }
+
+    pub fn press(&mut self, key: KeyCode) {
+        self.app
+            .world
+            .resource_mut::<Input<KeyCode>>()
+            .press(key);
+    }
+
+    pub fn release(&mut self, key: KeyCode) {
+        self.app
+            .world
+            .resource_mut::<Input<KeyCode>>()
+            .release(key);
+    }
+
+    pub fn press_mouse(&mut self, button: MouseButton) {
+        self.app
+            .world
+            .resource_mut::<Input<MouseButton>>()
+            .press(button);
+    }
+
+    pub fn release_mouse(&mut self, button: MouseButton) {
+        self.app
+            .world
+            .resource_mut::<Input<MouseButton>>()
+            .release(button);
+    }
+
+    /// Moves the mouse by `delta` pixels during the next step
+    pub fn move_mouse(&mut self, delta: Vec2) {
+        self.app.world.send_event(MouseMotion { delta });
+    }
+
+    /// Scrolls by `lines` during the next step
+    pub fn scroll(&mut self, lines: f32) {
+        self.app.world.send_event(MouseWheel {
+            unit: MouseScrollUnit::Line,
+            x: 0.0,
+            y: lines,
+        });
+    }
+
+    /// Scrolls a trackpad by `pixels` during the next step
+    pub fn scroll_pixels(&mut self, pixels: f32) {
+        self.app.world.send_event(MouseWheel {
+            unit: MouseScrollUnit::Pixel,
+            x: 0.0,
+            y: pixels,
+        });
+    }
+
+    /// Puts finger `id` down, moves or lifts it during the next step
+    pub fn touch(
+        &mut self,
+        id: u64,
+        phase: TouchPhase,
+        position: Vec2,
+    ) {
+        self.app.world.send_event(TouchInput {
+            phase,
+            position,
+            force: None,
+            id,
+        });
+    }
+
+    /// Runs `frames` updates, keys and buttons stay held in between
+    pub fn step(&mut self, frames: usize) {
+        for _ in 0..frames {
+            let mut time =
+                self.app.world.resource_mut::<Time>();
+            let last_update = time
+                .last_update()
+                .expect("time starts out updated");
+            time.update_with_instant(
+                last_update
+                    + Duration::from_secs_f32(FRAME),
+            );
+
+            self.app.update();
+
+            self.app
+                .world
+                .resource_mut::<Input<KeyCode>>()
+                .clear();
+            self.app
+                .world
+                .resource_mut::<Input<MouseButton>>()
+                .clear();
+        }
+    }
+
+    pub fn transform(&self) -> Transform {
+        *self
+            .app
+            .world
+            .get::<Transform>(self.camera)
+            .unwrap()
+    }
+
+    pub fn controller(&self) -> &CameraController {
+        self.app
+            .world
+            .get::<CameraController>(self.camera)
+            .unwrap()
+    }
+}
+
+pub fn assert_vec3_eq(actual: Vec3, expected: Vec3) {
+    assert!(
+        actual.abs_diff_eq(expected, 1e-3),
+        "expected {expected}, got {actual}"
+    );
+}
+
+pub fn assert_f32_eq(actual: f32, expected: f32) {
+    assert!(
+        (actual - expected).abs() < 1e-3,
+        "expected {expected}, got {actual}"
+    );
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_basic_camera::{
+    CameraController, CameraSettings, CameraSettingsFile,
+    CameraSettingsPlugin,
+};
+use common::*;
+use std::path::PathBuf;
+
+/// A settings file of its own for every test, as they run in
+/// parallel
+fn settings_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "bevy_basic_camera_{}_{}.ron",
+        name,
+        std::process::id()
+    ))
+}
+
+fn settings_file(path: PathBuf) -> CameraSettingsFile {
+    CameraSettingsFile { path, ..default() }
+}
+
+#[test]
+fn settings_round_trip_through_the_file() {
+    let path = settings_path("round_trip");
+    let tweaked = CameraController {
+        sensitivity: 0.25,
+        walk_speed: 7.0,
+        key_forward: KeyCode::Z,
+        mouse_key_pan: MouseButton::Right,
+        ..default()
+    };
+    let settings =
+        CameraSettings::from_controller(&tweaked);
+    settings_file(path.clone()).save(settings);
+
+    let mut file = settings_file(path.clone());
+    file.load();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(file.settings, Some(settings));
+
+    let mut controller = CameraController::default();
+    file.settings.unwrap().apply(&mut controller);
+    assert_eq!(
+        CameraSettings::from_controller(&controller),
+        settings,
+    );
+}
+
+#[test]
+fn partial_files_keep_the_defaults() {
+    let path = settings_path("partial");
+    // the README's example
+    std::fs::write(
+        &path,
+        "(\n    sensitivity: 0.5,\n    key_forward: Z,\n    \
+         key_left: Q,\n)\n",
+    )
+    .unwrap();
+
+    let mut file = settings_file(path.clone());
+    file.load();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(
+        file.settings,
+        Some(CameraSettings {
+            sensitivity: 0.5,
+            key_forward: KeyCode::Z,
+            key_left: KeyCode::Q,
+            ..default()
+        }),
+    );
+}
+
+#[test]
+fn the_plugin_loads_applies_and_saves() {
+    let path = settings_path("plugin");
+    std::fs::write(&path, "(sensitivity: 0.5)").unwrap();
+    let mut harness = CameraHarness::new(
+        Transform::default(),
+        CameraController::default(),
+    );
+    harness
+        .app
+        .insert_resource(settings_file(path.clone()))
+        .add_plugin(CameraSettingsPlugin);
+    harness.step(1);
+    assert_eq!(harness.controller().sensitivity, 0.5);
+
+    harness
+        .app
+        .world
+        .get_mut::<CameraController>(harness.camera)
+        .unwrap()
+        .walk_speed = 7.0;
+    harness.press(KeyCode::F5);
+    harness.step(1);
+
+    let mut file = settings_file(path.clone());
+    file.load();
+    std::fs::remove_file(&path).unwrap();
+    let saved = file.settings.unwrap();
+    assert_eq!(saved.sensitivity, 0.5);
+    assert_eq!(saved.walk_speed, 7.0);
+}
@@ -0,0 +1,177 @@
+mod common;
+
+use bevy::{input::touch::TouchPhase, prelude::*};
+use bevy_basic_camera::{
+    CameraController, CameraMode, TouchGesture,
+};
+use common::*;
+use std::f32::consts::FRAC_PI_2;
+
+fn orbit_camera() -> CameraHarness {
+    CameraHarness::new(
+        Transform::from_xyz(0.0, 0.0, 5.0)
+            .looking_at(Vec3::ZERO, Vec3::Y),
+        CameraController {
+            mode: CameraMode::Orbit,
+            ..default()
+        },
+    )
+}
+
+#[test]
+fn one_finger_drags() {
+    let gesture = TouchGesture::from_positions(&[(
+        Vec2::new(10.0, 10.0),
+        Vec2::new(15.0, 8.0),
+    )]);
+    assert_eq!(gesture.drag, Vec2::new(5.0, -2.0));
+    assert_eq!(gesture.pan, Vec2::ZERO);
+    assert_eq!(gesture.pinch, 1.0);
+}
+
+#[test]
+fn two_fingers_pan_pinch_and_rotate() {
+    // spread to twice the distance while turning a quarter clockwise
+    // and moving the middle 10 pixels right
+    let gesture = TouchGesture::from_positions(&[
+        (Vec2::new(-10.0, 0.0), Vec2::new(10.0, -20.0)),
+        (Vec2::new(10.0, 0.0), Vec2::new(10.0, 20.0)),
+    ]);
+    assert_vec3_eq(
+        gesture.pan.extend(0.0),
+        Vec3::new(10.0, 0.0, 0.0),
+    );
+    assert_f32_eq(gesture.pinch, 2.0);
+    assert_f32_eq(gesture.rotate, FRAC_PI_2);
+}
+
+#[test]
+fn three_fingers_are_no_gesture() {
+    let finger = (Vec2::ZERO, Vec2::ONE);
+    assert_eq!(
+        TouchGesture::from_positions(&[finger; 3]),
+        TouchGesture::default()
+    );
+}
+
+#[test]
+fn pinching_zooms_like_the_scroll_wheel() {
+    let gesture = TouchGesture {
+        pinch: 2.0,
+        ..default()
+    };
+    // halving the distance takes 5 steps of 10%
+    assert_f32_eq(gesture.zoom_steps(0.1), 5.0);
+}
+
+#[test]
+fn spreading_two_fingers_zooms_in() {
+    let mut harness = orbit_camera();
+    harness.touch(
+        0,
+        TouchPhase::Started,
+        Vec2::new(390.0, 300.0),
+    );
+    harness.touch(
+        1,
+        TouchPhase::Started,
+        Vec2::new(410.0, 300.0),
+    );
+    harness.step(1);
+    // landing doesn't move anything
+    assert_f32_eq(
+        harness.transform().translation.length(),
+        5.0,
+    );
+
+    harness.touch(
+        0,
+        TouchPhase::Moved,
+        Vec2::new(380.0, 300.0),
+    );
+    harness.touch(
+        1,
+        TouchPhase::Moved,
+        Vec2::new(420.0, 300.0),
+    );
+    harness.step(1);
+    assert_f32_eq(
+        harness.transform().translation.length(),
+        2.5,
+    );
+}
+
+#[test]
+fn one_finger_orbits() {
+    let mut harness = orbit_camera();
+    harness.touch(
+        0,
+        TouchPhase::Started,
+        Vec2::new(400.0, 300.0),
+    );
+    harness.step(1);
+    harness.touch(
+        0,
+        TouchPhase::Moved,
+        Vec2::new(400.0 + WINDOW_SIZE.x / 4.0, 300.0),
+    );
+    harness.step(1);
+    assert_vec3_eq(
+        harness.transform().translation,
+        Vec3::new(-5.0, 0.0, 0.0),
+    );
+
+    // a finger resting in place doesn't keep turning the camera
+    harness.step(5);
+    assert_vec3_eq(
+        harness.transform().translation,
+        Vec3::new(-5.0, 0.0, 0.0),
+    );
+}
+
+#[test]
+fn two_finger_drag_pans() {
+    let mut harness = orbit_camera();
+    harness.touch(
+        0,
+        TouchPhase::Started,
+        Vec2::new(390.0, 300.0),
+    );
+    harness.touch(
+        1,
+        TouchPhase::Started,
+        Vec2::new(410.0, 300.0),
+    );
+    harness.step(1);
+    harness.touch(
+        0,
+        TouchPhase::Moved,
+        Vec2::new(390.0, 360.0),
+    );
+    harness.touch(
+        1,
+        TouchPhase::Moved,
+        Vec2::new(410.0, 360.0),
+    );
+    harness.step(1);
+
+    let focus = harness.controller().orbit_focus;
+    assert!(focus.y > 0.0, "focus didn't follow: {focus}");
+    assert_f32_eq(focus.x, 0.0);
+    assert_f32_eq(harness.transform().translation.z, 5.0);
+}
+
+#[test]
+fn trackpad_scrolling_zooms() {
+    let mut harness = orbit_camera();
+    let pixels_per_line =
+        harness.controller().scroll_pixels_per_line;
+    harness.scroll_pixels(pixels_per_line);
+    harness.step(1);
+
+    let speed = harness.controller().scroll_wheel_speed;
+    assert_f32_eq(
+        harness.transform().translation.length(),
+        5.0 * (1.0 - speed),
+    );
+}
@@ -0,0 +1,49 @@
+#import bevy_sprite::mesh2d_view_bindings
+#import bevy_sprite::mesh2d_bindings
+
+// NOTE: Bindings must come before functions that use them!
+#import bevy_sprite::mesh2d_functions
+#import bevy_shader_utils::sdf_2d
+#import bevy_shader_utils::sdf_operators
+
+struct SdfMaterial {
+    color: vec4<f32>,
+    time: f32
+};
+
+@group(1) @binding(0)
+var<uniform> material: SdfMaterial;
+
+struct FragmentInput {
+    #import bevy_sprite::mesh2d_vertex_output
+};
+
+fn scene(p: vec2<f32>) -> f32 {
+    // a circle orbiting a star, melting into it as they meet
+    let orbit = vec2(cos(material.time), sin(material.time)) * 0.25;
+    let star = sdStar5(p, 0.18, 0.5);
+    let circle = sdCircle(p - orbit, 0.08);
+    let shapes = opSmoothUnion(star, circle, 0.08);
+
+    // a grid of hollow rounded boxes in the background
+    let cell = opRepeat2(p + vec2(0.0, material.time * 0.05), vec2(0.12));
+    let boxes = opOnion(opRound(sdBox2(cell, vec2(0.02)), 0.01), 0.004);
+
+    return opUnion(shapes, opSubtraction(boxes, shapes - 0.04));
+}
+
+@fragment
+fn fragment(
+    in: FragmentInput,
+) -> @location(0) vec4<f32> {
+    let d = scene(in.uv - 0.5);
+
+    // inside in the material color, outside dark, with distance bands
+    let inside = material.color.rgb;
+    let outside = vec3(0.08, 0.09, 0.12);
+    var color = mix(inside, outside, step(0.0, d));
+    color = color * (0.85 + 0.15 * cos(150.0 * d));
+    // an outline on the edge
+    color = mix(color, vec3(1.0), 1.0 - smoothstep(0.0, 0.004, abs(d)));
+    return vec4(color, 1.0);
+}
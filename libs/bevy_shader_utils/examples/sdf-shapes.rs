@@ -0,0 +1,75 @@
+//! Renders a quad with a custom [`Material2d`] that draws
+//! signed distance shapes, blending and repeating them over
+//! time.
+
+use bevy::{
+    prelude::*,
+    reflect::TypeUuid,
+    render::render_resource::{AsBindGroup, ShaderRef},
+    sprite::{
+        Material2d, Material2dPlugin, MaterialMesh2dBundle,
+    },
+};
+use bevy_shader_utils::ShaderUtilsPlugin;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(AssetPlugin {
+            watch_for_changes: true,
+            ..default()
+        }))
+        .add_plugin(ShaderUtilsPlugin)
+        .add_plugin(
+            Material2dPlugin::<SdfMaterial>::default(),
+        )
+        .add_startup_system(setup)
+        .add_system(update_time_in_shader)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<SdfMaterial>>,
+) {
+    let mesh = Mesh::from(shape::Quad::default());
+
+    // Spawn camera
+    commands.spawn(Camera2dBundle::default());
+
+    // Spawn the quad
+    commands.spawn(MaterialMesh2dBundle {
+        mesh: meshes.add(mesh).into(),
+        transform: Transform::from_scale(Vec3::splat(800.)),
+        material: materials.add(SdfMaterial {
+            color: Color::rgb(0.282, 0.51, 1.0),
+            time: 0.,
+        }),
+        ..default()
+    });
+}
+
+fn update_time_in_shader(
+    time: Res<Time>,
+    mut materials: ResMut<Assets<SdfMaterial>>,
+) {
+    for material in materials.iter_mut() {
+        material.1.time = time.elapsed_seconds();
+    }
+}
+
+impl Material2d for SdfMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/sdf_material.wgsl".into()
+    }
+}
+
+// This is the struct that will be passed to your shader
+#[derive(AsBindGroup, TypeUuid, Debug, Clone)]
+#[uuid = "5e8cb5a9-0a0f-4b55-9a54-2b8f0d3f6a21"]
+pub struct SdfMaterial {
+    #[uniform(0)]
+    color: Color,
+    #[uniform(0)]
+    time: f32,
+}
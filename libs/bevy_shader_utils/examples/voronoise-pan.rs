@@ -1,31 +1,24 @@
-//! Shows how to render a polygonal [`Mesh`], generated from a [`Quad`] primitive, in a 2D scene.
-//! Adds a texture and colored vertices, giving per-vertex tinting.
+//! Renders a quad with a custom [`Material2d`] that pans
+//! through voronoise.
+//! Run with `--features hot-reload` to edit
+//! `shaders/voronoise.wgsl` while it's running.
 
 use bevy::{
-    asset::AssetServerSettings,
     prelude::*,
     reflect::TypeUuid,
-    render::{
-        mesh::MeshVertexBufferLayout,
-        render_resource::{
-            AsBindGroup, RenderPipelineDescriptor,
-            ShaderRef, SpecializedMeshPipelineError,
-        },
-    },
+    render::render_resource::{AsBindGroup, ShaderRef},
     sprite::{
-        Material2d, Material2dKey, Material2dPlugin,
-        MaterialMesh2dBundle,
+        Material2d, Material2dPlugin, MaterialMesh2dBundle,
     },
 };
 use bevy_shader_utils::ShaderUtilsPlugin;
 
 fn main() {
     App::new()
-        .insert_resource(AssetServerSettings {
+        .add_plugins(DefaultPlugins.set(AssetPlugin {
             watch_for_changes: true,
             ..default()
-        })
-        .add_plugins(DefaultPlugins)
+        }))
         .add_plugin(ShaderUtilsPlugin)
         .add_plugin(
             Material2dPlugin::<CustomMaterial>::default(),
@@ -44,10 +37,10 @@ fn setup(
     let mesh = Mesh::from(shape::Quad::default());
 
     // Spawn camera
-    commands.spawn_bundle(Camera2dBundle::default());
+    commands.spawn(Camera2dBundle::default());
 
-    // Spawn the quad with vertex colors
-    commands.spawn_bundle(MaterialMesh2dBundle {
+    // Spawn the quad
+    commands.spawn(MaterialMesh2dBundle {
         mesh: meshes.add(mesh).into(),
         transform: Transform::from_translation(Vec3::new(
             0., 0., 0.,
@@ -55,7 +48,7 @@ fn setup(
         .with_scale(Vec3::splat(4024.)),
         material: materials.add(CustomMaterial {
             color: Color::RED,
-            time: time.seconds_since_startup() as f32,
+            time: time.elapsed_seconds(),
         }),
         ..default()
     });
@@ -66,36 +59,17 @@ fn update_time_in_shader(
     mut materials: ResMut<Assets<CustomMaterial>>,
 ) {
     for material in materials.iter_mut() {
-        material.1.time =
-            time.seconds_since_startup() as f32;
+        material.1.time = time.elapsed_seconds();
     }
 }
 
-/// The Material trait is very configurable, but comes with sensible defaults for all methods.
-/// You only need to implement functions for features that need non-default behavior. See the Material api docs for details!
+/// The Material2d trait is very configurable, but comes with sensible defaults for all methods.
+/// You only need to implement functions for features that need non-default behavior. See the Material2d api docs for details!
+/// The 2d mesh pipeline already sets `VERTEX_UVS` for meshes with uvs.
 impl Material2d for CustomMaterial {
     fn fragment_shader() -> ShaderRef {
         "shaders/custom_material.wgsl".into()
     }
-    fn vertex_shader() -> ShaderRef {
-        "shaders/custom_material.wgsl".into()
-    }
-
-    fn specialize(
-        descriptor: &mut RenderPipelineDescriptor,
-        _layout: &MeshVertexBufferLayout,
-        _key: Material2dKey<Self>,
-    ) -> Result<(), SpecializedMeshPipelineError> {
-        descriptor
-            .vertex
-            .shader_defs
-            .push("VERTEX_UVS".to_string());
-        let fragment =
-            descriptor.fragment.as_mut().unwrap();
-        fragment.shader_defs.push("VERTEX_UVS".to_string());
-
-        Ok(())
-    }
 }
 
 // This is the struct that will be passed to your shader
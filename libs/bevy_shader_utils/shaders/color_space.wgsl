@@ -0,0 +1,130 @@
+#define_import_path bevy_shader_utils::color_space
+
+// Conversions between color spaces. RGB is linear unless the name
+// says sRGB, and hues are in turns, 0 to 1, not degrees.
+
+fn srgbChannelToLinear(c: f32) -> f32 {
+  if (c <= 0.04045) {
+    return c / 12.92;
+  }
+  return pow((c + 0.055) / 1.055, 2.4);
+}
+
+fn linearChannelToSrgb(c: f32) -> f32 {
+  if (c <= 0.0031308) {
+    return c * 12.92;
+  }
+  return 1.055 * pow(c, 1. / 2.4) - 0.055;
+}
+
+fn srgbToLinear(c: vec3<f32>) -> vec3<f32> {
+  return vec3<f32>(
+    srgbChannelToLinear(c.x),
+    srgbChannelToLinear(c.y),
+    srgbChannelToLinear(c.z),
+  );
+}
+
+fn linearToSrgb(c: vec3<f32>) -> vec3<f32> {
+  return vec3<f32>(
+    linearChannelToSrgb(c.x),
+    linearChannelToSrgb(c.y),
+    linearChannelToSrgb(c.z),
+  );
+}
+
+// Rec. 709 luminance of a linear color
+fn relativeLuminance(c: vec3<f32>) -> f32 {
+  return dot(c, vec3<f32>(0.2126, 0.7152, 0.0722));
+}
+
+// the hue shared by HSV and HSL, 0 for grays
+fn rgbHue(c: vec3<f32>, high: f32, chroma: f32) -> f32 {
+  if (chroma <= 0.) {
+    return 0.;
+  }
+  var hue: f32;
+  if (high == c.x) {
+    hue = (c.y - c.z) / chroma;
+    if (hue < 0.) {
+      hue = hue + 6.;
+    }
+  } else if (high == c.y) {
+    hue = (c.z - c.x) / chroma + 2.;
+  } else {
+    hue = (c.x - c.y) / chroma + 4.;
+  }
+  return hue / 6.;
+}
+
+// red at 0, green at 1/3, blue at 2/3
+fn hueToRgb(hue: f32) -> vec3<f32> {
+  let k = abs(fract(hue + vec3<f32>(0., 2. / 3., 1. / 3.)) * 6. - 3.);
+  return clamp(k - 1., vec3<f32>(0.), vec3<f32>(1.));
+}
+
+fn rgbToHsv(c: vec3<f32>) -> vec3<f32> {
+  let high = max(c.x, max(c.y, c.z));
+  let low = min(c.x, min(c.y, c.z));
+  let chroma = high - low;
+  var saturation = 0.;
+  if (high > 0.) {
+    saturation = chroma / high;
+  }
+  return vec3<f32>(rgbHue(c, high, chroma), saturation, high);
+}
+
+fn hsvToRgb(c: vec3<f32>) -> vec3<f32> {
+  return c.z * mix(vec3<f32>(1.), hueToRgb(c.x), c.y);
+}
+
+fn rgbToHsl(c: vec3<f32>) -> vec3<f32> {
+  let high = max(c.x, max(c.y, c.z));
+  let low = min(c.x, min(c.y, c.z));
+  let chroma = high - low;
+  let lightness = (high + low) * 0.5;
+  var saturation = 0.;
+  if (chroma > 0.) {
+    saturation = chroma / (1. - abs(2. * lightness - 1.));
+  }
+  return vec3<f32>(rgbHue(c, high, chroma), saturation, lightness);
+}
+
+fn hslToRgb(c: vec3<f32>) -> vec3<f32> {
+  let chroma = (1. - abs(2. * c.z - 1.)) * c.y;
+  return c.z + chroma * (hueToRgb(c.x) - 0.5);
+}
+
+// Oklab, a perceptual color space where even steps look even,
+// for gradients that don't go muddy in the middle
+// https://bottosson.github.io/posts/oklab/
+// MIT License. © 2020 Björn Ottosson
+//
+fn linearToOklab(c: vec3<f32>) -> vec3<f32> {
+  let lms = mat3x3<f32>(
+    vec3<f32>(0.4122214708, 0.2119034982, 0.0883024619),
+    vec3<f32>(0.5363325363, 0.6806995451, 0.2817188376),
+    vec3<f32>(0.0514459929, 0.1073969566, 0.6299787005),
+  ) * c;
+  // a cube root that keeps the sign of colors out of gamut
+  let lms_cbrt = sign(lms) * pow(abs(lms), vec3<f32>(1. / 3.));
+  return mat3x3<f32>(
+    vec3<f32>(0.2104542553, 1.9779984951, 0.0259040371),
+    vec3<f32>(0.7936177850, -2.4285922050, 0.7827717662),
+    vec3<f32>(-0.0040720468, 0.4505937099, -0.8086757660),
+  ) * lms_cbrt;
+}
+
+fn oklabToLinear(c: vec3<f32>) -> vec3<f32> {
+  let lms_cbrt = mat3x3<f32>(
+    vec3<f32>(1., 1., 1.),
+    vec3<f32>(0.3963377774, -0.1055613458, -0.0894841775),
+    vec3<f32>(0.2158037573, -0.0638541728, -1.2914855480),
+  ) * c;
+  let lms = lms_cbrt * lms_cbrt * lms_cbrt;
+  return mat3x3<f32>(
+    vec3<f32>(4.0767416621, -1.2684380046, -0.0041960863),
+    vec3<f32>(-3.3077115913, 2.6097574011, -0.7034186147),
+    vec3<f32>(0.2309699292, -0.3413193965, 1.7076147010),
+  ) * lms;
+}
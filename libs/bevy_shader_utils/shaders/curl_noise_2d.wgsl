@@ -0,0 +1,13 @@
+#define_import_path bevy_shader_utils::curl_noise_2d
+
+// Curl noise, a divergence-free flow field: the gradient of simplexNoise2
+// turned by 90 degrees, so particles following it swirl without bunching
+// up.
+// needs bevy_shader_utils::noise_common and
+// bevy_shader_utils::simplex_noise_2d imported first
+//
+
+fn curlNoise2(p: vec2<f32>) -> vec2<f32> {
+  let gradient = simplexNoiseDeriv2(p).yz;
+  return vec2<f32>(gradient.y, -gradient.x);
+}
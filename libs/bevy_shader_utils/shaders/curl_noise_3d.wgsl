@@ -0,0 +1,15 @@
+#define_import_path bevy_shader_utils::curl_noise_3d
+
+// Curl noise, a divergence-free flow field: the curl of a vector potential
+// made of three offset simplexNoise3 fields, so particles following it
+// swirl without bunching up.
+// needs bevy_shader_utils::noise_common and
+// bevy_shader_utils::simplex_noise_3d imported first
+//
+
+fn curlNoise3(p: vec3<f32>) -> vec3<f32> {
+  let a = simplexNoiseDeriv3(p).yzw;
+  let b = simplexNoiseDeriv3(p + vec3<f32>(5.217, -7.351, 3.119)).yzw;
+  let c = simplexNoiseDeriv3(p + vec3<f32>(-8.743, 2.907, -6.431)).yzw;
+  return vec3<f32>(c.y - b.z, a.z - c.x, b.x - a.y);
+}
@@ -1,12 +1,18 @@
+#define_import_path bevy_shader_utils::fbm
+
 //  MIT License. © Inigo Quilez, Munrocket
-//  noise2() is any noise here: Value, Perlin, Simplex, Worley
+//  four octaves of value noise, see the fbm_* modules for configurable
+//  fractal noise over the other base noises
+//  needs bevy_shader_utils::value_noise_2d imported first
 //
-var m2: mat2x2<f32> = mat2x2<f32>(vec2<f32>(0.8, 0.6), vec2<f32>(-0.6, 0.8));
+
 fn fbm(p: vec2<f32>) -> f32 {
+  let m2 = mat2x2<f32>(vec2<f32>(0.8, 0.6), vec2<f32>(-0.6, 0.8));
+  var q: vec2<f32> = p;
   var f: f32 = 0.;
-  f = f + 0.5000 * noise2(p); p = m2 * p * 2.02;
-  f = f + 0.2500 * noise2(p); p = m2 * p * 2.03;
-  f = f + 0.1250 * noise2(p); p = m2 * p * 2.01;
-  f = f + 0.0625 * noise2(p);
+  f = f + 0.5000 * valueNoise2(q); q = m2 * q * 2.02;
+  f = f + 0.2500 * valueNoise2(q); q = m2 * q * 2.03;
+  f = f + 0.1250 * valueNoise2(q); q = m2 * q * 2.01;
+  f = f + 0.0625 * valueNoise2(q);
   return f / 0.9375;
-}
\ No newline at end of file
+}
@@ -0,0 +1,55 @@
+#define_import_path bevy_shader_utils::{{module}}
+
+// Fractal noise: octaves of {{noise}} at increasing frequency
+// (lacunarity) and decreasing amplitude (gain), normalized by the summed
+// amplitudes. Typical values are 5 octaves, lacunarity 2 and gain 0.5.
+// Generated by bevy_shader_utils from fbm_template.wgsl, once for every
+// base noise.
+// needs bevy_shader_utils::{{base}} imported first, and
+// bevy_shader_utils::noise_common before that for Perlin and Simplex
+//
+
+// the octave loop behind every shape: 0 is fBm, 1 turbulence, 2 ridged
+// and 3 billow
+fn {{prefix}}Octaves{{dim}}(p: {{vec}}, octaves: i32, lacunarity: f32, gain: f32, shape: i32) -> f32 {
+  var q: {{vec}} = p;
+  var amplitude: f32 = 1.;
+  var total: f32 = 0.;
+  var sum: f32 = 0.;
+  for (var i: i32 = 0; i < octaves; i = i + 1) {
+    let n = {{noise}}(q);
+    var v: f32 = n;
+    if (shape == 1) {
+      v = abs(n);
+    } else if (shape == 2) {
+      v = (1. - abs(n)) * (1. - abs(n));
+    } else if (shape == 3) {
+      v = 2. * abs(n) - 1.;
+    }
+    sum = sum + amplitude * v;
+    total = total + amplitude;
+    q = q * lacunarity + {{offset}};
+    amplitude = amplitude * gain;
+  }
+  return sum / max(total, 1e-6);
+}
+
+// fBm, in [-1, 1]
+fn {{prefix}}Fbm{{dim}}(p: {{vec}}, octaves: i32, lacunarity: f32, gain: f32) -> f32 {
+  return {{prefix}}Octaves{{dim}}(p, octaves, lacunarity, gain, 0);
+}
+
+// turbulence, the absolute noise, in [0, 1]
+fn {{prefix}}Turbulence{{dim}}(p: {{vec}}, octaves: i32, lacunarity: f32, gain: f32) -> f32 {
+  return {{prefix}}Octaves{{dim}}(p, octaves, lacunarity, gain, 1);
+}
+
+// sharp ridges where the noise crosses zero, in [0, 1]
+fn {{prefix}}Ridged{{dim}}(p: {{vec}}, octaves: i32, lacunarity: f32, gain: f32) -> f32 {
+  return {{prefix}}Octaves{{dim}}(p, octaves, lacunarity, gain, 2);
+}
+
+// rounded, cloudy lumps, in [-1, 1]
+fn {{prefix}}Billow{{dim}}(p: {{vec}}, octaves: i32, lacunarity: f32, gain: f32) -> f32 {
+  return {{prefix}}Octaves{{dim}}(p, octaves, lacunarity, gain, 3);
+}
@@ -0,0 +1,15 @@
+#define_import_path bevy_shader_utils::noise_common
+
+// MIT License. © Stefan Gustavson, Munrocket
+// helpers shared by the Perlin and Simplex noise modules, import this
+// once before any of them
+//
+fn permute3(x: vec3<f32>) -> vec3<f32> { return (((x * 34.) + 1.) * x) % vec3<f32>(289.); }
+fn permute4(x: vec4<f32>) -> vec4<f32> { return ((x * 34. + 1.) * x) % vec4<f32>(289.); }
+fn taylorInvSqrt4(r: vec4<f32>) -> vec4<f32> { return 1.79284291400159 - 0.85373472095314 * r; }
+fn fade2(t: vec2<f32>) -> vec2<f32> { return t * t * t * (t * (t * 6. - 15.) + 10.); }
+fn fade3(t: vec3<f32>) -> vec3<f32> { return t * t * t * (t * (t * 6. - 15.) + 10.); }
+fn permute1(x: f32) -> f32 { return ((x * 34. + 1.) * x) % 289.; }
+fn taylorInvSqrt1(r: f32) -> f32 { return 1.79284291400159 - 0.85373472095314 * r; }
+fn mod289_3(x: vec3<f32>) -> vec3<f32> { return x - floor(x / 289.) * 289.; }
+fn mod289_4(x: vec4<f32>) -> vec4<f32> { return x - floor(x / 289.) * 289.; }
@@ -0,0 +1,16 @@
+#define_import_path bevy_shader_utils::palette
+
+// Palettes from four colors, going around a cosine for each channel
+// as t goes from 0 to 1. a is the middle of the palette, b how far
+// it swings, c how many times it goes around and d where it starts.
+// https://iquilezles.org/articles/palettes/
+// MIT License. © Inigo Quilez
+//
+fn cosinePalette(t: f32, a: vec3<f32>, b: vec3<f32>, c: vec3<f32>, d: vec3<f32>) -> vec3<f32> {
+  return a + b * cos(6.28318530718 * (c * t + d));
+}
+
+// the rainbow from the article
+fn rainbowPalette(t: f32) -> vec3<f32> {
+  return cosinePalette(t, vec3<f32>(0.5), vec3<f32>(0.5), vec3<f32>(1.), vec3<f32>(0., 0.33, 0.67));
+}
@@ -0,0 +1,69 @@
+#define_import_path bevy_shader_utils::raymarch
+
+// Sphere tracing of a scene made of signed distances. The importing shader
+// defines the scene as
+//
+//   fn sceneSdf(p: vec3<f32>) -> f32
+//
+// https://iquilezles.org/articles/rmshadows/
+// https://iquilezles.org/articles/normalsSDF/
+// MIT License. © Inigo Quilez
+//
+
+// How far along the ray it hits the scene, or -1 when it doesn't within
+// `max_distance` and `max_steps`. `direction` needs to be normalized.
+fn raymarch(origin: vec3<f32>, direction: vec3<f32>, max_distance: f32, max_steps: i32) -> f32 {
+  var t: f32 = 0.;
+  for (var i: i32 = 0; i < max_steps; i++) {
+    let d = sceneSdf(origin + direction * t);
+    if (d < 0.0001 * max(t, 1.)) {
+      return t;
+    }
+    t += d;
+    if (t > max_distance) {
+      break;
+    }
+  }
+  return -1.;
+}
+
+// The surface normal at a point on it, from four samples of the scene
+fn raymarchNormal(p: vec3<f32>) -> vec3<f32> {
+  let k = vec2<f32>(1., -1.);
+  let h = 0.0001;
+  return normalize(
+    k.xyy * sceneSdf(p + k.xyy * h) +
+    k.yyx * sceneSdf(p + k.yyx * h) +
+    k.yxy * sceneSdf(p + k.yxy * h) +
+    k.xxx * sceneSdf(p + k.xxx * h)
+  );
+}
+
+// How lit a point is from a light in `direction`, 0 in shadow, 1 lit, with
+// a penumbra that gets sharper as k grows
+fn raymarchSoftShadow(origin: vec3<f32>, direction: vec3<f32>, min_t: f32, max_t: f32, k: f32) -> f32 {
+  var res: f32 = 1.;
+  var t: f32 = min_t;
+  for (var i: i32 = 0; i < 64 && t < max_t; i++) {
+    let h = sceneSdf(origin + direction * t);
+    res = min(res, k * h / t);
+    if (res < 0.001) {
+      break;
+    }
+    t += clamp(h, 0.01, 0.2);
+  }
+  return clamp(res, 0., 1.);
+}
+
+// How much of the ambient light reaches a point with normal n, 1 for all
+fn raymarchAmbientOcclusion(p: vec3<f32>, n: vec3<f32>) -> f32 {
+  var occlusion: f32 = 0.;
+  var scale: f32 = 1.;
+  for (var i: i32 = 0; i < 5; i++) {
+    let h = 0.01 + 0.12 * f32(i) / 4.;
+    let d = sceneSdf(p + h * n);
+    occlusion += (h - d) * scale;
+    scale *= 0.95;
+  }
+  return clamp(1. - 3. * occlusion, 0., 1.);
+}
@@ -0,0 +1,58 @@
+#define_import_path bevy_shader_utils::sdf_2d
+
+// 2D signed distance functions, negative inside the shape and positive
+// outside. Shapes are centered on the origin, combine and reshape them
+// with bevy_shader_utils::sdf_operators.
+// https://iquilezles.org/articles/distfunctions2d/
+// MIT License. © Inigo Quilez
+//
+fn sdCircle(p: vec2<f32>, r: f32) -> f32 {
+  return length(p) - r;
+}
+
+// b is the half size
+fn sdBox2(p: vec2<f32>, b: vec2<f32>) -> f32 {
+  let d = abs(p) - b;
+  return length(max(d, vec2<f32>(0.))) + min(max(d.x, d.y), 0.);
+}
+
+// the line from a to b, round it with opRound for a thickness
+fn sdSegment2(p: vec2<f32>, a: vec2<f32>, b: vec2<f32>) -> f32 {
+  let pa = p - a;
+  let ba = b - a;
+  let h = clamp(dot(pa, ba) / dot(ba, ba), 0., 1.);
+  return length(pa - ba * h);
+}
+
+// pointing up, r is half the side
+fn sdEquilateralTriangle(p: vec2<f32>, r: f32) -> f32 {
+  let k = sqrt(3.);
+  var q: vec2<f32> = vec2<f32>(abs(p.x) - r, p.y + r / k);
+  if (q.x + k * q.y > 0.) {
+    q = vec2<f32>(q.x - k * q.y, -k * q.x - q.y) / 2.;
+  }
+  q.x = q.x - clamp(q.x, -2. * r, 0.);
+  return -length(q) * sign(q.y);
+}
+
+// flat top and bottom, r is the distance to them
+fn sdHexagon(p: vec2<f32>, r: f32) -> f32 {
+  let k = vec3<f32>(-0.866025404, 0.5, 0.577350269);
+  var q: vec2<f32> = abs(p);
+  q = q - 2. * min(dot(k.xy, q), 0.) * k.xy;
+  q = q - vec2<f32>(clamp(q.x, -k.z * r, k.z * r), r);
+  return length(q) * sign(q.y);
+}
+
+// r is the outer radius, rf in [0, 1] how far in the inner corners are
+fn sdStar5(p: vec2<f32>, r: f32, rf: f32) -> f32 {
+  let k1 = vec2<f32>(0.809016994375, -0.587785252292);
+  let k2 = vec2<f32>(-k1.x, k1.y);
+  var q: vec2<f32> = vec2<f32>(abs(p.x), p.y);
+  q = q - 2. * max(dot(k1, q), 0.) * k1;
+  q = q - 2. * max(dot(k2, q), 0.) * k2;
+  q = vec2<f32>(abs(q.x), q.y - r);
+  let ba = rf * vec2<f32>(-k1.y, k1.x) - vec2<f32>(0., 1.);
+  let h = clamp(dot(q, ba) / dot(ba, ba), 0., r);
+  return length(q - ba * h) * sign(q.y * ba.x - q.x * ba.y);
+}
@@ -0,0 +1,42 @@
+#define_import_path bevy_shader_utils::sdf_3d
+
+// 3D signed distance functions, negative inside the shape and positive
+// outside. Shapes are centered on the origin, combine and reshape them
+// with bevy_shader_utils::sdf_operators.
+// https://iquilezles.org/articles/distfunctions/
+// MIT License. © Inigo Quilez
+//
+fn sdSphere(p: vec3<f32>, r: f32) -> f32 {
+  return length(p) - r;
+}
+
+// b is the half size
+fn sdBox3(p: vec3<f32>, b: vec3<f32>) -> f32 {
+  let q = abs(p) - b;
+  return length(max(q, vec3<f32>(0.))) + min(max(q.x, max(q.y, q.z)), 0.);
+}
+
+// around the y axis, t.x is the radius of the ring and t.y of the tube
+fn sdTorus(p: vec3<f32>, t: vec2<f32>) -> f32 {
+  let q = vec2<f32>(length(p.xz) - t.x, p.y);
+  return length(q) - t.y;
+}
+
+// from a to b
+fn sdCapsule(p: vec3<f32>, a: vec3<f32>, b: vec3<f32>, r: f32) -> f32 {
+  let pa = p - a;
+  let ba = b - a;
+  let h = clamp(dot(pa, ba) / dot(ba, ba), 0., 1.);
+  return length(pa - ba * h) - r;
+}
+
+// around the y axis, h is the half height
+fn sdCylinder(p: vec3<f32>, h: f32, r: f32) -> f32 {
+  let d = abs(vec2<f32>(length(p.xz), p.y)) - vec2<f32>(r, h);
+  return min(max(d.x, d.y), 0.) + length(max(d, vec2<f32>(0.)));
+}
+
+// n needs to be normalized, h is the distance from the origin
+fn sdPlane(p: vec3<f32>, n: vec3<f32>, h: f32) -> f32 {
+  return dot(p, n) + h;
+}
@@ -0,0 +1,64 @@
+#define_import_path bevy_shader_utils::sdf_operators
+
+// Operators on signed distances, for the 2D and 3D ones alike.
+// https://iquilezles.org/articles/distfunctions/
+// https://iquilezles.org/articles/smin/
+// MIT License. © Inigo Quilez
+//
+fn opUnion(a: f32, b: f32) -> f32 {
+  return min(a, b);
+}
+
+// a with b cut out of it
+fn opSubtraction(a: f32, b: f32) -> f32 {
+  return max(a, -b);
+}
+
+fn opIntersection(a: f32, b: f32) -> f32 {
+  return max(a, b);
+}
+
+// k is how far apart the shapes start blending
+fn opSmoothUnion(a: f32, b: f32, k: f32) -> f32 {
+  let h = clamp(0.5 + 0.5 * (b - a) / k, 0., 1.);
+  return mix(b, a, h) - k * h * (1. - h);
+}
+
+fn opSmoothSubtraction(a: f32, b: f32, k: f32) -> f32 {
+  let h = clamp(0.5 - 0.5 * (a + b) / k, 0., 1.);
+  return mix(a, -b, h) + k * h * (1. - h);
+}
+
+fn opSmoothIntersection(a: f32, b: f32, k: f32) -> f32 {
+  let h = clamp(0.5 - 0.5 * (b - a) / k, 0., 1.);
+  return mix(b, a, h) + k * h * (1. - h);
+}
+
+// grows the shape by r, rounding its corners
+fn opRound(d: f32, r: f32) -> f32 {
+  return d - r;
+}
+
+// hollows the shape out, leaving a shell of the given thickness
+fn opOnion(d: f32, thickness: f32) -> f32 {
+  return abs(d) - thickness;
+}
+
+// Domain repetition: pass the point through these before the shape, to
+// repeat it every `spacing`
+fn opRepeat2(p: vec2<f32>, spacing: vec2<f32>) -> vec2<f32> {
+  return p - spacing * round(p / spacing);
+}
+
+fn opRepeat3(p: vec3<f32>, spacing: vec3<f32>) -> vec3<f32> {
+  return p - spacing * round(p / spacing);
+}
+
+// repeats `limit` times each way from the origin, 2 * limit + 1 in all
+fn opRepeatLimited2(p: vec2<f32>, spacing: vec2<f32>, limit: vec2<f32>) -> vec2<f32> {
+  return p - spacing * clamp(round(p / spacing), -limit, limit);
+}
+
+fn opRepeatLimited3(p: vec3<f32>, spacing: vec3<f32>, limit: vec3<f32>) -> vec3<f32> {
+  return p - spacing * clamp(round(p / spacing), -limit, limit);
+}
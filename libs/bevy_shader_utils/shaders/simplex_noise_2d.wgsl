@@ -1,12 +1,13 @@
+#define_import_path bevy_shader_utils::simplex_noise_2d
+
 //  MIT License. © Ian McEwan, Stefan Gustavson, Munrocket
+//  needs bevy_shader_utils::noise_common imported first
 //
-fn permute3(x: vec3<f32>) -> vec3<f32> { return (((x * 34.) + 1.) * x) % vec3<f32>(289.); }
-
 fn simplexNoise2(v: vec2<f32>) -> f32 {
   let C = vec4<f32>(0.211324865405187, 0.366025403784439, -0.577350269189626, 0.024390243902439);
   var i: vec2<f32> = floor(v + dot(v, C.yy));
   let x0 = v - i + dot(i, C.xx);
-  var i1: vec2<f32> = select(vec2<f32>(1., 0.), vec2<f32>(0., 1.), (x0.x > x0.y));
+  var i1: vec2<f32> = select(vec2<f32>(0., 1.), vec2<f32>(1., 0.), (x0.x > x0.y));
   var x12: vec4<f32> = x0.xyxy + C.xxzz - vec4<f32>(i1, 0., 0.);
   i = i % vec2<f32>(289.);
   let p = permute3(permute3(i.y + vec3<f32>(0., i1.y, 1.)) + i.x + vec3<f32>(0., i1.x, 1.));
@@ -21,4 +22,93 @@ fn simplexNoise2(v: vec2<f32>) -> f32 {
   m = m * (1.79284291400159 - 0.85373472095314 * (a0 * a0 + h * h));
   let g = vec3<f32>(a0.x * x0.x + h.x * x0.y, a0.yz * x12.xz + h.yz * x12.yw);
   return 130. * dot(m, g);
-}
\ No newline at end of file
+}
+
+// simplexNoise2 and its gradient, as (value, d/dx, d/dy)
+fn simplexNoiseDeriv2(v: vec2<f32>) -> vec3<f32> {
+  let C = vec4<f32>(0.211324865405187, 0.366025403784439, -0.577350269189626, 0.024390243902439);
+  var i: vec2<f32> = floor(v + dot(v, C.yy));
+  let x0 = v - i + dot(i, C.xx);
+  var i1: vec2<f32> = select(vec2<f32>(0., 1.), vec2<f32>(1., 0.), (x0.x > x0.y));
+  var x12: vec4<f32> = x0.xyxy + C.xxzz - vec4<f32>(i1, 0., 0.);
+  i = i % vec2<f32>(289.);
+  let p = permute3(permute3(i.y + vec3<f32>(0., i1.y, 1.)) + i.x + vec3<f32>(0., i1.x, 1.));
+  let m = max(0.5 -
+      vec3<f32>(dot(x0, x0), dot(x12.xy, x12.xy), dot(x12.zw, x12.zw)), vec3<f32>(0.));
+  let m2 = m * m;
+  let m3 = m2 * m;
+  let m4 = m2 * m2;
+  let x = 2. * fract(p * C.www) - 1.;
+  let h = abs(x) - 0.5;
+  let ox = floor(x + 0.5);
+  let a0 = x - ox;
+  // the gradients of the three corners, normalised
+  let norm = 1.79284291400159 - 0.85373472095314 * (a0 * a0 + h * h);
+  let g0 = vec2<f32>(a0.x, h.x) * norm.x;
+  let g1 = vec2<f32>(a0.y, h.y) * norm.y;
+  let g2 = vec2<f32>(a0.z, h.z) * norm.z;
+  let d = vec3<f32>(dot(g0, x0), dot(g1, x12.xy), dot(g2, x12.zw));
+  let value = dot(m4, d);
+  let gradient = m4.x * g0 + m4.y * g1 + m4.z * g2
+      - 8. * (m3.x * d.x * x0 + m3.y * d.y * x12.xy + m3.z * d.z * x12.zw);
+  return 130. * vec3<f32>(value, gradient);
+}
+
+// Simplex noise repeating every `period`, which needs whole numbers, the y
+// one even. It's the tiling noise of psrdnoise, on a lattice that lines up
+// with the axes, so it looks like but doesn't match simplexNoise2.
+// MIT License. © Stefan Gustavson, Ian McEwan
+fn simplexNoisePeriodic2(x: vec2<f32>, period: vec2<f32>) -> f32 {
+  // Transform to simplex space (axis-aligned hexagonal grid)
+  let uv = vec2<f32>(x.x + x.y * 0.5, x.y);
+
+  // Determine which simplex we're in, with i0 being the "base"
+  let i0 = floor(uv);
+  let f0 = fract(uv);
+  // o1 is the offset in simplex space to the second corner
+  let cmp = step(f0.y, f0.x);
+  let o1 = vec2<f32>(cmp, 1. - cmp);
+
+  // Transform corners back to texture space
+  let v0 = vec2<f32>(i0.x - i0.y * 0.5, i0.y);
+  let v1 = vec2<f32>(v0.x + o1.x - o1.y * 0.5, v0.y + o1.y);
+  let v2 = vec2<f32>(v0.x + 0.5, v0.y + 1.);
+
+  // Compute vectors from x to each of the simplex corners
+  let x0 = x - v0;
+  let x1 = x - v1;
+  let x2 = x - v2;
+
+  // Wrap to periods and transform back to simplex space, fixing rounding errors
+  var xw: vec3<f32> = vec3<f32>(v0.x, v1.x, v2.x);
+  var yw: vec3<f32> = vec3<f32>(v0.y, v1.y, v2.y);
+  xw = xw - floor(xw / period.x) * period.x;
+  yw = yw - floor(yw / period.y) * period.y;
+  let iu = floor(xw + 0.5 * yw + 0.5);
+  let iv = floor(yw + 0.5);
+
+  // Compute one pseudo-random hash value for each corner
+  var hash: vec3<f32> = mod289_3(iu);
+  hash = mod289_3((hash * 51. + 2.) * hash + iv);
+  hash = mod289_3((hash * 34. + 10.) * hash);
+
+  // Pick a pseudo-random angle for each gradient
+  let psi = hash * 0.07482;
+  let gx = cos(psi);
+  let gy = sin(psi);
+  let g0 = vec2<f32>(gx.x, gy.x);
+  let g1 = vec2<f32>(gx.y, gy.y);
+  let g2 = vec2<f32>(gx.z, gy.z);
+
+  // Radial decay with distance from each simplex corner
+  var w: vec3<f32> = 0.8 - vec3<f32>(dot(x0, x0), dot(x1, x1), dot(x2, x2));
+  w = max(w, vec3<f32>(0.));
+  let w2 = w * w;
+  let w4 = w2 * w2;
+
+  // The value of the linear ramp from each of the corners
+  let gdotx = vec3<f32>(dot(g0, x0), dot(g1, x1), dot(g2, x2));
+
+  // Multiply by the radial decay and sum up the noise value, scaled to [-1, 1]
+  return 10.9 * dot(w4, gdotx);
+}
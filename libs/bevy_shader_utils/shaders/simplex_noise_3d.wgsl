@@ -1,8 +1,8 @@
+#define_import_path bevy_shader_utils::simplex_noise_3d
+
 //  MIT License. © Ian McEwan, Stefan Gustavson, Munrocket
+//  needs bevy_shader_utils::noise_common imported first
 //
-fn permute4(x: vec4<f32>) -> vec4<f32> { return ((x * 34. + 1.) * x) % vec4<f32>(289.); }
-fn taylorInvSqrt4(r: vec4<f32>) -> vec4<f32> { return 1.79284291400159 - 0.85373472095314 * r; }
-
 fn simplexNoise3(v: vec3<f32>) -> f32 {
   let C = vec2<f32>(1. / 6., 1. / 3.);
   let D = vec4<f32>(0., 0.5, 1., 2.);
@@ -69,4 +69,160 @@ fn simplexNoise3(v: vec3<f32>) -> f32 {
   m = max(m, vec4<f32>(0.));
   m = m * m;
   return 42. * dot(m * m, vec4<f32>(dot(p0,x0), dot(p1,x1), dot(p2,x2), dot(p3,x3)));
-}
\ No newline at end of file
+}
+
+// simplexNoise3 and its gradient, as (value, d/dx, d/dy, d/dz)
+fn simplexNoiseDeriv3(v: vec3<f32>) -> vec4<f32> {
+  let C = vec2<f32>(1. / 6., 1. / 3.);
+  let D = vec4<f32>(0., 0.5, 1., 2.);
+
+  // First corner
+  var i: vec3<f32>  = floor(v + dot(v, C.yyy));
+  let x0 = v - i + dot(i, C.xxx);
+
+  // Other corners
+  let g = step(x0.yzx, x0.xyz);
+  let l = 1.0 - g;
+  let i1 = min(g.xyz, l.zxy);
+  let i2 = max(g.xyz, l.zxy);
+
+  // x0 = x0 - 0. + 0. * C
+  let x1 = x0 - i1 + 1. * C.xxx;
+  let x2 = x0 - i2 + 2. * C.xxx;
+  let x3 = x0 - 1. + 3. * C.xxx;
+
+  // Permutations
+  i = i % vec3<f32>(289.);
+  let p = permute4(permute4(permute4(
+      i.z + vec4<f32>(0., i1.z, i2.z, 1. )) +
+      i.y + vec4<f32>(0., i1.y, i2.y, 1. )) +
+      i.x + vec4<f32>(0., i1.x, i2.x, 1. ));
+
+  // Gradients (NxN points uniformly over a square, mapped onto an octahedron.)
+  var n_: f32 = 1. / 7.; // N=7
+  let ns = n_ * D.wyz - D.xzx;
+
+  let j = p - 49. * floor(p * ns.z * ns.z); // mod(p, N*N)
+
+  let x_ = floor(j * ns.z);
+  let y_ = floor(j - 7.0 * x_); // mod(j, N)
+
+  let x = x_ *ns.x + ns.yyyy;
+  let y = y_ *ns.x + ns.yyyy;
+  let h = 1.0 - abs(x) - abs(y);
+
+  let b0 = vec4<f32>( x.xy, y.xy );
+  let b1 = vec4<f32>( x.zw, y.zw );
+
+  let s0 = floor(b0)*2.0 + 1.0;
+  let s1 = floor(b1)*2.0 + 1.0;
+  let sh = -step(h, vec4<f32>(0.));
+
+  let a0 = b0.xzyw + s0.xzyw*sh.xxyy ;
+  let a1 = b1.xzyw + s1.xzyw*sh.zzww ;
+
+  var p0: vec3<f32> = vec3<f32>(a0.xy, h.x);
+  var p1: vec3<f32> = vec3<f32>(a0.zw, h.y);
+  var p2: vec3<f32> = vec3<f32>(a1.xy, h.z);
+  var p3: vec3<f32> = vec3<f32>(a1.zw, h.w);
+
+  // Normalise gradients
+  let norm = taylorInvSqrt4(vec4<f32>(dot(p0,p0), dot(p1,p1), dot(p2,p2), dot(p3,p3)));
+  p0 = p0 * norm.x;
+  p1 = p1 * norm.y;
+  p2 = p2 * norm.z;
+  p3 = p3 * norm.w;
+
+  // Mix final noise value, and its gradient
+  let m = max(0.6 - vec4<f32>(dot(x0,x0), dot(x1,x1), dot(x2,x2), dot(x3,x3)), vec4<f32>(0.));
+  let m2 = m * m;
+  let m3 = m2 * m;
+  let m4 = m2 * m2;
+  let d = vec4<f32>(dot(p0,x0), dot(p1,x1), dot(p2,x2), dot(p3,x3));
+  let value = dot(m4, d);
+  let gradient = m4.x * p0 + m4.y * p1 + m4.z * p2 + m4.w * p3
+      - 8. * (m3.x * d.x * x0 + m3.y * d.y * x1 + m3.z * d.z * x2 + m3.w * d.w * x3);
+  return 42. * vec4<f32>(value, gradient);
+}
+
+fn simplexPeriodicPermute4(i: vec4<f32>) -> vec4<f32> {
+  let im = mod289_4(i);
+  return mod289_4((im * 34. + 10.) * im);
+}
+
+// Simplex noise repeating every `period`, which needs whole numbers. It's
+// the tiling noise of psrdnoise, on a lattice of its own, so it looks like
+// but doesn't match simplexNoise3.
+// MIT License. © Stefan Gustavson, Ian McEwan
+fn simplexNoisePeriodic3(x: vec3<f32>, period: vec3<f32>) -> f32 {
+  let M = mat3x3<f32>(vec3<f32>(0., 1., 1.), vec3<f32>(1., 0., 1.), vec3<f32>(1., 1., 0.));
+  let Mi = mat3x3<f32>(vec3<f32>(-0.5, 0.5, 0.5), vec3<f32>(0.5, -0.5, 0.5), vec3<f32>(0.5, 0.5, -0.5));
+
+  // Transform to simplex space (tetrahedral grid)
+  let uvw = M * x;
+
+  // Determine which simplex we're in, i0 is the "base corner"
+  let i0 = floor(uvw);
+  let f0 = fract(uvw);
+
+  // Rank order the magnitudes of u, v, w, resolving ties in priority order
+  // u, v, w. o1, o2 are offsets in simplex space to the 2nd and 3rd corners.
+  let g_ = step(f0.xyx, f0.yzz);
+  let l_ = 1. - g_;
+  let g = vec3<f32>(l_.z, g_.xy);
+  let l = vec3<f32>(l_.xy, g_.z);
+  let o1 = min(g, l);
+  let o2 = max(g, l);
+
+  // Transform the corners back to texture space
+  let v0 = Mi * i0;
+  let v1 = Mi * (i0 + o1);
+  let v2 = Mi * (i0 + o2);
+  let v3 = Mi * (i0 + vec3<f32>(1.));
+
+  // Compute vectors to each of the simplex corners
+  let x0 = x - v0;
+  let x1 = x - v1;
+  let x2 = x - v2;
+  let x3 = x - v3;
+
+  // Wrap to periods and transform back to simplex space, fixing rounding errors
+  var vx: vec4<f32> = vec4<f32>(v0.x, v1.x, v2.x, v3.x);
+  var vy: vec4<f32> = vec4<f32>(v0.y, v1.y, v2.y, v3.y);
+  var vz: vec4<f32> = vec4<f32>(v0.z, v1.z, v2.z, v3.z);
+  vx = vx - floor(vx / period.x) * period.x;
+  vy = vy - floor(vy / period.y) * period.y;
+  vz = vz - floor(vz / period.z) * period.z;
+  let j0 = floor(M * vec3<f32>(vx.x, vy.x, vz.x) + 0.5);
+  let j1 = floor(M * vec3<f32>(vx.y, vy.y, vz.y) + 0.5);
+  let j2 = floor(M * vec3<f32>(vx.z, vy.z, vz.z) + 0.5);
+  let j3 = floor(M * vec3<f32>(vx.w, vy.w, vz.w) + 0.5);
+
+  // Compute one pseudo-random hash value for each corner
+  let hash = simplexPeriodicPermute4(simplexPeriodicPermute4(simplexPeriodicPermute4(
+      vec4<f32>(j0.z, j1.z, j2.z, j3.z))
+      + vec4<f32>(j0.y, j1.y, j2.y, j3.y))
+      + vec4<f32>(j0.x, j1.x, j2.x, j3.x));
+
+  // Gradients from a Fibonacci spiral on the unit sphere
+  let theta = hash * 3.883222077; // 2*pi/golden ratio
+  let sz = hash * -0.006920415 + 0.996539792; // 1-(hash+0.5)*2/289
+  let sz_prime = sqrt(1. - sz * sz);
+  let gx = cos(theta) * sz_prime;
+  let gy = sin(theta) * sz_prime;
+  let g0 = vec3<f32>(gx.x, gy.x, sz.x);
+  let g1 = vec3<f32>(gx.y, gy.y, sz.y);
+  let g2 = vec3<f32>(gx.z, gy.z, sz.z);
+  let g3 = vec3<f32>(gx.w, gy.w, sz.w);
+
+  // Radial decay with distance from each simplex corner
+  var w: vec4<f32> = 0.5 - vec4<f32>(dot(x0, x0), dot(x1, x1), dot(x2, x2), dot(x3, x3));
+  w = max(w, vec4<f32>(0.));
+  let w3 = w * w * w;
+
+  // The value of the linear ramp from each of the corners
+  let gdotx = vec4<f32>(dot(g0, x0), dot(g1, x1), dot(g2, x2), dot(g3, x3));
+
+  // Multiply by the radial decay and sum up the noise value, scaled to [-1, 1]
+  return 39.5 * dot(w3, gdotx);
+}
@@ -0,0 +1,77 @@
+#define_import_path bevy_shader_utils::simplex_noise_4d
+
+//  MIT License. © Ian McEwan, Stefan Gustavson, Munrocket
+//  needs bevy_shader_utils::noise_common imported first
+//
+fn grad4(j: f32, ip: vec4<f32>) -> vec4<f32> {
+  let xyz = floor(fract(vec3<f32>(j) * ip.xyz) * 7.) * ip.z - 1.;
+  let w = 1.5 - dot(abs(xyz), vec3<f32>(1.));
+  // 1 where negative
+  let s = 1. - step(vec4<f32>(0.), vec4<f32>(xyz, w));
+  return vec4<f32>(xyz + (s.xyz * 2. - 1.) * s.www, w);
+}
+
+fn simplexNoise4(v: vec4<f32>) -> f32 {
+  let F4 = 0.309016994374947451;
+  let C = vec4<f32>(
+    0.138196601125011,  // (5 - sqrt(5))/20  G4
+    0.276393202250021,  // 2 * G4
+    0.414589803375032,  // 3 * G4
+    -0.447213595499958, // -1 + 4 * G4
+  );
+
+  // First corner
+  var i: vec4<f32> = floor(v + dot(v, vec4<f32>(F4)));
+  let x0 = v - i + dot(i, C.xxxx);
+
+  // Other corners, ranked by the size of the components of x0
+  let isX = step(x0.yzw, x0.xxx);
+  let isYZ = step(x0.zww, x0.yyz);
+  var i0: vec4<f32> = vec4<f32>(isX.x + isX.y + isX.z, 1. - isX);
+  i0.y = i0.y + isYZ.x + isYZ.y;
+  i0.z = i0.z + 1. - isYZ.x + isYZ.z;
+  i0.w = i0.w + 2. - isYZ.y - isYZ.z;
+
+  let i3 = clamp(i0, vec4<f32>(0.), vec4<f32>(1.));
+  let i2 = clamp(i0 - 1., vec4<f32>(0.), vec4<f32>(1.));
+  let i1 = clamp(i0 - 2., vec4<f32>(0.), vec4<f32>(1.));
+
+  let x1 = x0 - i1 + C.xxxx;
+  let x2 = x0 - i2 + C.yyyy;
+  let x3 = x0 - i3 + C.zzzz;
+  let x4 = x0 + C.wwww;
+
+  // Permutations
+  i = i % vec4<f32>(289.);
+  let j0 = permute1(permute1(permute1(permute1(i.w) + i.z) + i.y) + i.x);
+  let j1 = permute4(permute4(permute4(permute4(
+      i.w + vec4<f32>(i1.w, i2.w, i3.w, 1.)) +
+      i.z + vec4<f32>(i1.z, i2.z, i3.z, 1.)) +
+      i.y + vec4<f32>(i1.y, i2.y, i3.y, 1.)) +
+      i.x + vec4<f32>(i1.x, i2.x, i3.x, 1.));
+
+  // Gradients: 7x7x6 points over a cube, mapped onto a 4-cross polytope
+  let ip = vec4<f32>(1. / 294., 1. / 49., 1. / 7., 0.);
+
+  var p0: vec4<f32> = grad4(j0, ip);
+  var p1: vec4<f32> = grad4(j1.x, ip);
+  var p2: vec4<f32> = grad4(j1.y, ip);
+  var p3: vec4<f32> = grad4(j1.z, ip);
+  var p4: vec4<f32> = grad4(j1.w, ip);
+
+  // Normalise gradients
+  let norm = taylorInvSqrt4(vec4<f32>(dot(p0, p0), dot(p1, p1), dot(p2, p2), dot(p3, p3)));
+  p0 = p0 * norm.x;
+  p1 = p1 * norm.y;
+  p2 = p2 * norm.z;
+  p3 = p3 * norm.w;
+  p4 = p4 * taylorInvSqrt1(dot(p4, p4));
+
+  // Mix contributions from the five corners
+  var m0: vec3<f32> = max(0.6 - vec3<f32>(dot(x0, x0), dot(x1, x1), dot(x2, x2)), vec3<f32>(0.));
+  var m1: vec2<f32> = max(0.6 - vec2<f32>(dot(x3, x3), dot(x4, x4)), vec2<f32>(0.));
+  m0 = m0 * m0;
+  m1 = m1 * m1;
+  return 49. * (dot(m0 * m0, vec3<f32>(dot(p0, x0), dot(p1, x1), dot(p2, x2)))
+              + dot(m1 * m1, vec2<f32>(dot(p3, x3), dot(p4, x4))));
+}
@@ -0,0 +1,34 @@
+#define_import_path bevy_shader_utils::tonemapping
+
+// Tonemapping curves, squeezing linear HDR colors into 0 to 1
+// before they go to an sRGB target
+
+fn tonemapReinhard(c: vec3<f32>) -> vec3<f32> {
+  return c / (1. + c);
+}
+
+// Reinhard that reaches 1 at white instead of at infinity
+fn tonemapReinhardExtended(c: vec3<f32>, white: f32) -> vec3<f32> {
+  return c * (1. + c / (white * white)) / (1. + c);
+}
+
+// Reinhard on the luminance, which keeps the hue of bright colors
+// instead of washing them out towards white per channel
+fn tonemapReinhardLuminance(c: vec3<f32>) -> vec3<f32> {
+  let luminance = dot(c, vec3<f32>(0.2126, 0.7152, 0.0722));
+  return c / (1. + luminance);
+}
+
+// Krzysztof Narkowicz's fit of the ACES filmic curve
+// https://knarkowicz.wordpress.com/2016/01/06/aces-filmic-tone-mapping-curve/
+// CC0
+//
+fn tonemapAcesFilmic(color: vec3<f32>) -> vec3<f32> {
+  let a = 2.51;
+  let b = 0.03;
+  let c = 2.43;
+  let d = 0.59;
+  let e = 0.14;
+  let x = color;
+  return clamp((x * (a * x + b)) / (x * (c * x + d) + e), vec3<f32>(0.), vec3<f32>(1.));
+}
@@ -0,0 +1,33 @@
+#define_import_path bevy_shader_utils::value_noise_2d
+
+// Value noise, random values on the lattice smoothly interpolated
+// between. Returns values in [-1, 1] like the Perlin and Simplex noise.
+//
+fn valueHash2(p: vec2<f32>) -> f32 { return fract(sin(dot(p, vec2<f32>(127.1, 311.7))) * 43758.5453); }
+
+fn valueNoise2(p: vec2<f32>) -> f32 {
+  let i = floor(p);
+  let f = fract(p);
+  let u = f * f * f * (f * (f * 6. - 15.) + 10.);
+  let a = valueHash2(i);
+  let b = valueHash2(i + vec2<f32>(1., 0.));
+  let c = valueHash2(i + vec2<f32>(0., 1.));
+  let d = valueHash2(i + vec2<f32>(1., 1.));
+  return 2. * mix(mix(a, b, u.x), mix(c, d, u.x), u.y) - 1.;
+}
+
+// valueNoise2 and its gradient, as (value, d/dx, d/dy)
+fn valueNoiseDeriv2(p: vec2<f32>) -> vec3<f32> {
+  let i = floor(p);
+  let f = fract(p);
+  let u = f * f * f * (f * (f * 6. - 15.) + 10.);
+  let du = 30. * f * f * (f * (f - 2.) + 1.);
+  let a = valueHash2(i);
+  let b = valueHash2(i + vec2<f32>(1., 0.));
+  let c = valueHash2(i + vec2<f32>(0., 1.));
+  let d = valueHash2(i + vec2<f32>(1., 1.));
+  let k = a - b - c + d;
+  let value = a + (b - a) * u.x + (c - a) * u.y + k * u.x * u.y;
+  let gradient = du * (vec2<f32>(b - a, c - a) + k * u.yx);
+  return vec3<f32>(2. * value - 1., 2. * gradient);
+}
@@ -0,0 +1,55 @@
+#define_import_path bevy_shader_utils::value_noise_3d
+
+// Value noise, random values on the lattice smoothly interpolated
+// between. Returns values in [-1, 1] like the Perlin and Simplex noise.
+//
+fn valueHash3(p: vec3<f32>) -> f32 { return fract(sin(dot(p, vec3<f32>(127.1, 311.7, 74.7))) * 43758.5453); }
+
+fn valueNoise3(p: vec3<f32>) -> f32 {
+  let i = floor(p);
+  let f = fract(p);
+  let u = f * f * f * (f * (f * 6. - 15.) + 10.);
+  let n000 = valueHash3(i);
+  let n100 = valueHash3(i + vec3<f32>(1., 0., 0.));
+  let n010 = valueHash3(i + vec3<f32>(0., 1., 0.));
+  let n110 = valueHash3(i + vec3<f32>(1., 1., 0.));
+  let n001 = valueHash3(i + vec3<f32>(0., 0., 1.));
+  let n101 = valueHash3(i + vec3<f32>(1., 0., 1.));
+  let n011 = valueHash3(i + vec3<f32>(0., 1., 1.));
+  let n111 = valueHash3(i + vec3<f32>(1., 1., 1.));
+  let n_x = mix(vec4<f32>(n000, n010, n001, n011), vec4<f32>(n100, n110, n101, n111), u.x);
+  let n_xy = mix(n_x.xz, n_x.yw, u.y);
+  return 2. * mix(n_xy.x, n_xy.y, u.z) - 1.;
+}
+
+// valueNoise3 and its gradient, as (value, d/dx, d/dy, d/dz)
+fn valueNoiseDeriv3(p: vec3<f32>) -> vec4<f32> {
+  let i = floor(p);
+  let f = fract(p);
+  let u = f * f * f * (f * (f * 6. - 15.) + 10.);
+  let du = 30. * f * f * (f * (f - 2.) + 1.);
+  let n000 = valueHash3(i);
+  let n100 = valueHash3(i + vec3<f32>(1., 0., 0.));
+  let n010 = valueHash3(i + vec3<f32>(0., 1., 0.));
+  let n110 = valueHash3(i + vec3<f32>(1., 1., 0.));
+  let n001 = valueHash3(i + vec3<f32>(0., 0., 1.));
+  let n101 = valueHash3(i + vec3<f32>(1., 0., 1.));
+  let n011 = valueHash3(i + vec3<f32>(0., 1., 1.));
+  let n111 = valueHash3(i + vec3<f32>(1., 1., 1.));
+  // the trilinear blend as a polynomial in u
+  let k1 = n100 - n000;
+  let k2 = n010 - n000;
+  let k3 = n001 - n000;
+  let k4 = n000 - n100 - n010 + n110;
+  let k5 = n000 - n010 - n001 + n011;
+  let k6 = n000 - n100 - n001 + n101;
+  let k7 = -n000 + n100 + n010 - n110 + n001 - n101 - n011 + n111;
+  let value = n000 + k1 * u.x + k2 * u.y + k3 * u.z
+      + k4 * u.x * u.y + k5 * u.y * u.z + k6 * u.z * u.x + k7 * u.x * u.y * u.z;
+  let gradient = du * vec3<f32>(
+    k1 + k4 * u.y + k6 * u.z + k7 * u.y * u.z,
+    k2 + k5 * u.z + k4 * u.x + k7 * u.z * u.x,
+    k3 + k6 * u.x + k5 * u.y + k7 * u.x * u.y,
+  );
+  return vec4<f32>(2. * value - 1., 2. * gradient);
+}
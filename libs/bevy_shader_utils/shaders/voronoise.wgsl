@@ -1,3 +1,5 @@
+#define_import_path bevy_shader_utils::voronoise
+
 // The MIT License
 // https://www.youtube.com/c/InigoQuilez
 // https://iquilezles.org/
@@ -62,4 +64,28 @@ fn voronoise( p: vec2<f32>, u: f32, v: f32 ) -> f32
     }
     
     return a.x/a.y;
-}
\ No newline at end of file
+}
+
+// voronoise repeating every `period`, which needs whole numbers
+fn voronoisePeriodic( p: vec2<f32>, u: f32, v: f32, period: vec2<f32> ) -> f32
+{
+    let k: f32 = 1.0+63.0*pow(1.0-v,6.0);
+
+    let i: vec2<f32>= floor(p);
+    let f: vec2<f32>= fract(p);
+    
+    var a: vec2<f32>= vec2(0.0,0.0);
+    for (var y = -2; y<=2; y++) {
+    for (var x = -2; x<=2; x++)
+    {
+       let g: vec2<f32> = vec2<f32>( f32(x), f32(y) );
+        let c: vec2<f32> = i + g;
+        let o: vec3<f32> = hash3( c - floor(c/period)*period ) * vec3(u,u,1.0);
+        let d: vec2<f32> = g - f + o.xy;
+        let w: f32 = pow( 1.0-smoothstep(0.0,1.414,length(d)), k );
+        a += vec2(o.z*w,w);
+    }
+    }
+    
+    return a.x/a.y;
+}
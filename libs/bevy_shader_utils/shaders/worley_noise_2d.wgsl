@@ -0,0 +1,29 @@
+#define_import_path bevy_shader_utils::worley_noise_2d
+
+// Worley (cellular) noise, one randomly placed feature point per cell.
+// Returns the distances to the nearest and second nearest feature points,
+// F1 in x and F2 in y. F2 - F1 outlines the cells.
+//
+fn worleyHash2(p: vec2<f32>) -> vec2<f32> {
+  let q = vec2<f32>(dot(p, vec2<f32>(127.1, 311.7)), dot(p, vec2<f32>(269.5, 183.3)));
+  return fract(sin(q) * 43758.5453);
+}
+
+fn worleyNoise2(p: vec2<f32>) -> vec2<f32> {
+  let n = floor(p);
+  let f = fract(p);
+  var d: vec2<f32> = vec2<f32>(8.);
+  // two cells out, so F2 is never missed
+  for (var j: i32 = -2; j <= 2; j = j + 1) {
+    for (var i: i32 = -2; i <= 2; i = i + 1) {
+      let g = vec2<f32>(f32(i), f32(j));
+      let dist = length(g + worleyHash2(n + g) - f);
+      if (dist < d.x) {
+        d = vec2<f32>(dist, d.x);
+      } else if (dist < d.y) {
+        d.y = dist;
+      }
+    }
+  }
+  return d;
+}
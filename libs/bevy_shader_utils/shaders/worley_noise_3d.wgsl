@@ -0,0 +1,35 @@
+#define_import_path bevy_shader_utils::worley_noise_3d
+
+// Worley (cellular) noise, one randomly placed feature point per cell.
+// Returns the distances to the nearest and second nearest feature points,
+// F1 in x and F2 in y. F2 - F1 outlines the cells.
+//
+fn worleyHash3(p: vec3<f32>) -> vec3<f32> {
+  let q = vec3<f32>(
+    dot(p, vec3<f32>(127.1, 311.7, 74.7)),
+    dot(p, vec3<f32>(269.5, 183.3, 246.1)),
+    dot(p, vec3<f32>(113.5, 271.9, 124.6)),
+  );
+  return fract(sin(q) * 43758.5453);
+}
+
+fn worleyNoise3(p: vec3<f32>) -> vec2<f32> {
+  let n = floor(p);
+  let f = fract(p);
+  var d: vec2<f32> = vec2<f32>(8.);
+  // the neighbouring cells only, F2 can rarely be a little too far
+  for (var k: i32 = -1; k <= 1; k = k + 1) {
+    for (var j: i32 = -1; j <= 1; j = j + 1) {
+      for (var i: i32 = -1; i <= 1; i = i + 1) {
+        let g = vec3<f32>(f32(i), f32(j), f32(k));
+        let dist = length(g + worleyHash3(n + g) - f);
+        if (dist < d.x) {
+          d = vec2<f32>(dist, d.x);
+        } else if (dist < d.y) {
+          d.y = dist;
+        }
+      }
+    }
+  }
+  return d;
+}
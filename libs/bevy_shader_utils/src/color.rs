@@ -0,0 +1,230 @@
+//! Rust ports of the `color_space`, `palette` and `tonemapping`
+//! modules, so colors picked on the CPU, like in a UI, come out
+//! the same as in the shaders. Hues are in turns, 0 to 1.
+
+// the constants are kept exactly as the WGSL spells them
+#![allow(clippy::excessive_precision)]
+
+use bevy::math::{Mat3, Vec3};
+use std::f32::consts::TAU;
+
+const REC_709: Vec3 = Vec3::new(0.2126, 0.7152, 0.0722);
+
+fn srgb_channel_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_channel_to_srgb(c: f32) -> f32 {
+    if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1. / 2.4) - 0.055
+    }
+}
+
+/// `srgbToLinear` from `bevy_shader_utils::color_space`
+pub fn srgb_to_linear(c: Vec3) -> Vec3 {
+    Vec3::from_array(
+        c.to_array().map(srgb_channel_to_linear),
+    )
+}
+
+/// `linearToSrgb` from `bevy_shader_utils::color_space`
+pub fn linear_to_srgb(c: Vec3) -> Vec3 {
+    Vec3::from_array(
+        c.to_array().map(linear_channel_to_srgb),
+    )
+}
+
+/// `relativeLuminance` from `bevy_shader_utils::color_space`
+pub fn relative_luminance(c: Vec3) -> f32 {
+    c.dot(REC_709)
+}
+
+fn rgb_hue(c: Vec3, high: f32, chroma: f32) -> f32 {
+    if chroma <= 0. {
+        return 0.;
+    }
+    let hue = if high == c.x {
+        let hue = (c.y - c.z) / chroma;
+        if hue < 0. {
+            hue + 6.
+        } else {
+            hue
+        }
+    } else if high == c.y {
+        (c.z - c.x) / chroma + 2.
+    } else {
+        (c.x - c.y) / chroma + 4.
+    };
+    hue / 6.
+}
+
+fn hue_to_rgb(hue: f32) -> Vec3 {
+    let k = ((Vec3::splat(hue)
+        + Vec3::new(0., 2. / 3., 1. / 3.))
+    .fract()
+        * 6.
+        - 3.)
+        .abs();
+    (k - 1.).clamp(Vec3::ZERO, Vec3::ONE)
+}
+
+/// `rgbToHsv` from `bevy_shader_utils::color_space`
+pub fn rgb_to_hsv(c: Vec3) -> Vec3 {
+    let high = c.max_element();
+    let low = c.min_element();
+    let chroma = high - low;
+    let saturation =
+        if high > 0. { chroma / high } else { 0. };
+    Vec3::new(rgb_hue(c, high, chroma), saturation, high)
+}
+
+/// `hsvToRgb` from `bevy_shader_utils::color_space`
+pub fn hsv_to_rgb(c: Vec3) -> Vec3 {
+    c.z * Vec3::ONE.lerp(hue_to_rgb(c.x), c.y)
+}
+
+/// `rgbToHsl` from `bevy_shader_utils::color_space`
+pub fn rgb_to_hsl(c: Vec3) -> Vec3 {
+    let high = c.max_element();
+    let low = c.min_element();
+    let chroma = high - low;
+    let lightness = (high + low) * 0.5;
+    let saturation = if chroma > 0. {
+        chroma / (1. - (2. * lightness - 1.).abs())
+    } else {
+        0.
+    };
+    Vec3::new(
+        rgb_hue(c, high, chroma),
+        saturation,
+        lightness,
+    )
+}
+
+/// `hslToRgb` from `bevy_shader_utils::color_space`
+pub fn hsl_to_rgb(c: Vec3) -> Vec3 {
+    let chroma = (1. - (2. * c.z - 1.).abs()) * c.y;
+    c.z + chroma * (hue_to_rgb(c.x) - 0.5)
+}
+
+/// `linearToOklab` from `bevy_shader_utils::color_space`
+pub fn linear_to_oklab(c: Vec3) -> Vec3 {
+    let lms = Mat3::from_cols(
+        Vec3::new(0.4122214708, 0.2119034982, 0.0883024619),
+        Vec3::new(0.5363325363, 0.6806995451, 0.2817188376),
+        Vec3::new(0.0514459929, 0.1073969566, 0.6299787005),
+    ) * c;
+    let lms_cbrt = lms.signum()
+        * Vec3::from_array(
+            lms.abs().to_array().map(|x| x.powf(1. / 3.)),
+        );
+    Mat3::from_cols(
+        Vec3::new(0.2104542553, 1.9779984951, 0.0259040371),
+        Vec3::new(
+            0.7936177850,
+            -2.4285922050,
+            0.7827717662,
+        ),
+        Vec3::new(
+            -0.0040720468,
+            0.4505937099,
+            -0.8086757660,
+        ),
+    ) * lms_cbrt
+}
+
+/// `oklabToLinear` from `bevy_shader_utils::color_space`
+pub fn oklab_to_linear(c: Vec3) -> Vec3 {
+    let lms_cbrt = Mat3::from_cols(
+        Vec3::ONE,
+        Vec3::new(
+            0.3963377774,
+            -0.1055613458,
+            -0.0894841775,
+        ),
+        Vec3::new(
+            0.2158037573,
+            -0.0638541728,
+            -1.2914855480,
+        ),
+    ) * c;
+    let lms = lms_cbrt * lms_cbrt * lms_cbrt;
+    Mat3::from_cols(
+        Vec3::new(
+            4.0767416621,
+            -1.2684380046,
+            -0.0041960863,
+        ),
+        Vec3::new(
+            -3.3077115913,
+            2.6097574011,
+            -0.7034186147,
+        ),
+        Vec3::new(
+            0.2309699292,
+            -0.3413193965,
+            1.7076147010,
+        ),
+    ) * lms
+}
+
+/// `cosinePalette` from `bevy_shader_utils::palette`
+pub fn cosine_palette(
+    t: f32,
+    a: Vec3,
+    b: Vec3,
+    c: Vec3,
+    d: Vec3,
+) -> Vec3 {
+    let angle = TAU * (c * t + d);
+    a + b * Vec3::new(
+        angle.x.cos(),
+        angle.y.cos(),
+        angle.z.cos(),
+    )
+}
+
+/// `rainbowPalette` from `bevy_shader_utils::palette`
+pub fn rainbow_palette(t: f32) -> Vec3 {
+    cosine_palette(
+        t,
+        Vec3::splat(0.5),
+        Vec3::splat(0.5),
+        Vec3::ONE,
+        Vec3::new(0., 0.33, 0.67),
+    )
+}
+
+/// `tonemapReinhard` from `bevy_shader_utils::tonemapping`
+pub fn tonemap_reinhard(c: Vec3) -> Vec3 {
+    c / (1. + c)
+}
+
+/// `tonemapReinhardExtended` from
+/// `bevy_shader_utils::tonemapping`
+pub fn tonemap_reinhard_extended(
+    c: Vec3,
+    white: f32,
+) -> Vec3 {
+    c * (1. + c / (white * white)) / (1. + c)
+}
+
+/// `tonemapReinhardLuminance` from
+/// `bevy_shader_utils::tonemapping`
+pub fn tonemap_reinhard_luminance(c: Vec3) -> Vec3 {
+    c / (1. + c.dot(REC_709))
+}
+
+/// `tonemapAcesFilmic` from `bevy_shader_utils::tonemapping`
+pub fn tonemap_aces_filmic(color: Vec3) -> Vec3 {
+    let (a, b, c, d, e) = (2.51, 0.03, 2.43, 0.59, 0.14);
+    let x = color;
+    ((x * (a * x + b)) / (x * (c * x + d) + e))
+        .clamp(Vec3::ZERO, Vec3::ONE)
+}
@@ -0,0 +1,17 @@
+use bevy::math::Vec3;
+
+/// `mock_fresnel` from `bevy_shader_utils::mock_fresnel`, 0 where the
+/// surface faces the camera and `strength` where it's seen edge on
+pub fn mock_fresnel(
+    camera_view_world_position: Vec3,
+    world_position: Vec3,
+    world_normal: Vec3,
+    power: f32,
+    strength: f32,
+) -> f32 {
+    let v = (camera_view_world_position - world_position)
+        .normalize();
+    let n_dot_v = world_normal.dot(v).max(0.0001);
+    let fresnel = (1. - n_dot_v).clamp(0., 1.);
+    fresnel.powf(power) * strength
+}
@@ -1,8 +1,21 @@
+#[cfg(feature = "hot-reload")]
+use bevy::utils::HashMap;
 use bevy::{asset::HandleId, prelude::*};
+use std::borrow::Cow;
+
+mod color;
+mod fresnel;
+mod noise;
+
+pub use color::*;
+pub use fresnel::*;
+pub use noise::*;
 
 // some wgsl from https://gist.github.com/munrocket/236ed5ba7e409b8bdf1ff6eca5dcdc39
 
 // Noise Functions
+pub const NOISE_COMMON: &str =
+    include_str!("../shaders/noise_common.wgsl");
 pub const PERLIN_NOISE_2D: &str =
     include_str!("../shaders/perlin_noise_2d.wgsl");
 pub const PERLIN_NOISE_3D: &str =
@@ -11,85 +24,407 @@ pub const SIMPLEX_NOISE_2D: &str =
     include_str!("../shaders/simplex_noise_2d.wgsl");
 pub const SIMPLEX_NOISE_3D: &str =
     include_str!("../shaders/simplex_noise_3d.wgsl");
+pub const SIMPLEX_NOISE_4D: &str =
+    include_str!("../shaders/simplex_noise_4d.wgsl");
+pub const VALUE_NOISE_2D: &str =
+    include_str!("../shaders/value_noise_2d.wgsl");
+pub const VALUE_NOISE_3D: &str =
+    include_str!("../shaders/value_noise_3d.wgsl");
+pub const WORLEY_NOISE_2D: &str =
+    include_str!("../shaders/worley_noise_2d.wgsl");
+pub const WORLEY_NOISE_3D: &str =
+    include_str!("../shaders/worley_noise_3d.wgsl");
+pub const CURL_NOISE_2D: &str =
+    include_str!("../shaders/curl_noise_2d.wgsl");
+pub const CURL_NOISE_3D: &str =
+    include_str!("../shaders/curl_noise_3d.wgsl");
 pub const FBM: &str = include_str!("../shaders/fbm.wgsl");
+/// The `fbm_*` modules are generated from this, once for every
+/// base noise in [`FBM_NOISES`], see [`fbm_module`]
+pub const FBM_TEMPLATE: &str =
+    include_str!("../shaders/fbm_template.wgsl");
 pub const VORONOISE: &str =
     include_str!("../shaders/voronoise.wgsl");
+// Signed distance functions
+pub const SDF_2D: &str =
+    include_str!("../shaders/sdf_2d.wgsl");
+pub const SDF_3D: &str =
+    include_str!("../shaders/sdf_3d.wgsl");
+pub const SDF_OPERATORS: &str =
+    include_str!("../shaders/sdf_operators.wgsl");
+pub const RAYMARCH: &str =
+    include_str!("../shaders/raymarch.wgsl");
 // other utility functions
 pub const MOCK_FRESNEL: &str =
     include_str!("../shaders/mock_fresnel.wgsl");
+pub const COLOR_SPACE: &str =
+    include_str!("../shaders/color_space.wgsl");
+pub const PALETTE: &str =
+    include_str!("../shaders/palette.wgsl");
+pub const TONEMAPPING: &str =
+    include_str!("../shaders/tonemapping.wgsl");
+
+/// The built-in modules with a file of their own, by name under
+/// the `bevy_shader_utils` namespace. [`built_in_modules`] adds the
+/// generated `fbm_*` ones.
+pub const MODULES: &[(&str, &str)] = &[
+    ("noise_common", NOISE_COMMON),
+    ("perlin_noise_2d", PERLIN_NOISE_2D),
+    ("perlin_noise_3d", PERLIN_NOISE_3D),
+    ("simplex_noise_2d", SIMPLEX_NOISE_2D),
+    ("simplex_noise_3d", SIMPLEX_NOISE_3D),
+    ("simplex_noise_4d", SIMPLEX_NOISE_4D),
+    ("value_noise_2d", VALUE_NOISE_2D),
+    ("value_noise_3d", VALUE_NOISE_3D),
+    ("worley_noise_2d", WORLEY_NOISE_2D),
+    ("worley_noise_3d", WORLEY_NOISE_3D),
+    ("curl_noise_2d", CURL_NOISE_2D),
+    ("curl_noise_3d", CURL_NOISE_3D),
+    ("fbm", FBM),
+    ("voronoise", VORONOISE),
+    ("sdf_2d", SDF_2D),
+    ("sdf_3d", SDF_3D),
+    ("sdf_operators", SDF_OPERATORS),
+    ("raymarch", RAYMARCH),
+    ("mock_fresnel", MOCK_FRESNEL),
+    ("color_space", COLOR_SPACE),
+    ("palette", PALETTE),
+    ("tonemapping", TONEMAPPING),
+];
+
+/// The base noises with an `fbm_*` module, by function prefix
+/// and dimensions
+pub const FBM_NOISES: &[(&str, u32)] = &[
+    ("perlin", 2),
+    ("perlin", 3),
+    ("simplex", 2),
+    ("simplex", 3),
+    ("value", 2),
+    ("value", 3),
+];
+
+pub const NAMESPACE: &str = "bevy_shader_utils";
+
+/// The name and source of the `fbm_{noise}_{dimensions}d` module,
+/// [`FBM_TEMPLATE`] run over `{noise}Noise{dimensions}` from
+/// `{noise}_noise_{dimensions}d`
+pub fn fbm_module(
+    noise: &str,
+    dimensions: u32,
+) -> (String, String) {
+    let name = format!("fbm_{}_{}d", noise, dimensions);
+    let offset = match dimensions {
+        2 => "vec2<f32>(17.3, 41.9)",
+        _ => "vec3<f32>(17.3, 41.9, 29.1)",
+    };
+    let source = FBM_TEMPLATE
+        .replace("{{module}}", &name)
+        .replace(
+            "{{base}}",
+            &format!("{}_noise_{}d", noise, dimensions),
+        )
+        .replace(
+            "{{noise}}",
+            &format!("{}Noise{}", noise, dimensions),
+        )
+        .replace("{{prefix}}", noise)
+        .replace("{{dim}}", &dimensions.to_string())
+        .replace(
+            "{{vec}}",
+            &format!("vec{}<f32>", dimensions),
+        )
+        .replace("{{offset}}", offset);
+    (name, source)
+}
+
+/// Every built-in module by name, [`MODULES`] followed by the
+/// generated `fbm_*` ones
+pub fn built_in_modules(
+) -> impl Iterator<Item = (Cow<'static, str>, Cow<'static, str>)>
+{
+    MODULES
+        .iter()
+        .map(|(name, source)| {
+            (Cow::Borrowed(*name), Cow::Borrowed(*source))
+        })
+        .chain(FBM_NOISES.iter().map(
+            |(noise, dimensions)| {
+                let (name, source) =
+                    fbm_module(noise, *dimensions);
+                (Cow::Owned(name), Cow::Owned(source))
+            },
+        ))
+}
+
 pub struct ShaderUtilsPlugin;
 
 impl Plugin for ShaderUtilsPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<ShaderUtils>();
+        #[cfg(feature = "hot-reload")]
+        app.add_system_to_stage(
+            CoreStage::Last,
+            keep_overrides,
+        );
+    }
+}
+
+/// A WGSL module shaders can `#import`
+#[derive(Debug, Clone)]
+pub struct WgslModule {
+    /// What goes after `#import`, like
+    /// `bevy_shader_utils::voronoise`
+    pub import_path: String,
+    pub handle: Handle<Shader>,
+    /// The source it was registered with, the baked-in one
+    /// for built-in modules even when they're hot-reloaded
+    pub source: Cow<'static, str>,
+}
+
+/// The importable modules, the built-in ones and any registered
+/// on top
+/// ```ignore
+///  fn setup(
+///      mut shader_utils: ResMut<ShaderUtils>,
+///      mut shaders: ResMut<Assets<Shader>>,
+///  ) {
+///      // `#import my_game::terrain` in your shaders
+///      shader_utils.register(
+///          &mut shaders,
+///          "my_game",
+///          "terrain",
+///          include_str!("terrain.wgsl"),
+///      );
+///      // every shader importing voronoise gets the patched one
+///      shader_utils.register(
+///          &mut shaders,
+///          bevy_shader_utils::NAMESPACE,
+///          "voronoise",
+///          include_str!("my_voronoise.wgsl"),
+///      );
+///  }
+/// ```
+#[derive(Resource, Debug)]
+pub struct ShaderUtils {
+    modules: Vec<WgslModule>,
+    /// The overridden modules loaded from disk, with how many
+    /// asset events the overrides themselves have yet to send
+    #[cfg(feature = "hot-reload")]
+    overrides: HashMap<HandleId, usize>,
+}
+
+impl ShaderUtils {
+    pub fn iter(
+        &self,
+    ) -> impl Iterator<Item = &WgslModule> {
+        self.modules.iter()
+    }
+
+    pub fn get(
+        &self,
+        import_path: &str,
+    ) -> Option<&WgslModule> {
+        self.modules.iter().find(|module| {
+            module.import_path == import_path
+        })
+    }
+
+    /// The handle of the module imported as `import_path`
+    pub fn handle(
+        &self,
+        import_path: &str,
+    ) -> Option<Handle<Shader>> {
+        self.get(import_path)
+            .map(|module| module.handle.clone_weak())
+    }
+
+    /// Makes `source` importable as `namespace::name`. A module
+    /// that's already registered under that path, built-in ones
+    /// included, is replaced in place, so shaders importing it
+    /// pick up the new source. With `hot-reload` the override
+    /// stays put when the module's file changes.
+    pub fn register(
+        &mut self,
+        shaders: &mut Assets<Shader>,
+        namespace: &str,
+        name: &str,
+        source: impl Into<Cow<'static, str>>,
+    ) -> Handle<Shader> {
+        let import_path =
+            format!("{}::{}", namespace, name);
+        let source = source.into();
+        let shader =
+            module_shader(&import_path, source.clone());
+
+        // a module loaded from disk keeps its strong handle, or
+        // the asset would be freed from under the override
+        let handle = match self.get(&import_path) {
+            Some(module) => module.handle.clone(),
+            None => {
+                Handle::weak(HandleId::random::<Shader>())
+            }
+        };
+        shaders.set_untracked(handle.clone_weak(), shader);
+        #[cfg(feature = "hot-reload")]
+        if handle.is_strong() {
+            *self
+                .overrides
+                .entry(handle.id())
+                .or_default() += 1;
+        }
+
+        let module = WgslModule {
+            import_path,
+            handle: handle.clone(),
+            source,
+        };
+        match self.modules.iter_mut().find(|existing| {
+            existing.import_path == module.import_path
+        }) {
+            Some(existing) => *existing = module,
+            None => self.modules.push(module),
+        }
+        handle.clone_weak()
     }
 }
 
-#[allow(dead_code)]
-#[derive(Resource)]
-struct ShaderUtils {
-    perlin_noise_2d: HandleId,
-    perlin_noise_3d: HandleId,
-    simplex_noise_2d: HandleId,
-    simplex_noise_3d: HandleId,
-    fbm: HandleId,
-    voronoise: HandleId,
-    mock_fresnel: HandleId,
+fn module_shader(
+    import_path: &str,
+    source: Cow<'static, str>,
+) -> Shader {
+    let mut shader = Shader::from_wgsl(source);
+    shader.set_import_path(import_path);
+    shader
 }
 
 impl FromWorld for ShaderUtils {
     fn from_world(world: &mut World) -> Self {
+        let mut shader_utils = ShaderUtils {
+            modules: Vec::new(),
+            #[cfg(feature = "hot-reload")]
+            overrides: HashMap::default(),
+        };
+        #[cfg(feature = "hot-reload")]
+        shader_utils.load_from_disk(world);
+
         let mut shaders = world
             .get_resource_mut::<Assets<Shader>>()
             .unwrap();
+        for (name, source) in built_in_modules() {
+            // unless it came from disk already
+            let import_path =
+                format!("{}::{}", NAMESPACE, name);
+            if shader_utils.get(&import_path).is_none() {
+                shader_utils.register(
+                    &mut shaders,
+                    NAMESPACE,
+                    &name,
+                    source,
+                );
+            }
+        }
+        shader_utils
+    }
+}
 
-        ShaderUtils {
-            perlin_noise_2d: load_shader(
-                &mut shaders,
-                "perlin_noise_2d",
-                PERLIN_NOISE_2D,
-            ),
-            perlin_noise_3d: load_shader(
-                &mut shaders,
-                "perlin_noise_3d",
-                PERLIN_NOISE_3D,
-            ),
-            simplex_noise_2d: load_shader(
-                &mut shaders,
-                "simplex_noise_2d",
-                SIMPLEX_NOISE_2D,
-            ),
-            simplex_noise_3d: load_shader(
-                &mut shaders,
-                "simplex_noise_3d",
-                SIMPLEX_NOISE_3D,
-            ),
-            fbm: load_shader(&mut shaders, "fbm", FBM),
-            voronoise: load_shader(
-                &mut shaders,
-                "voronoise",
-                VORONOISE,
-            ),
-            mock_fresnel: load_shader(
-                &mut shaders,
-                "mock_fresnel",
-                MOCK_FRESNEL,
-            ),
+#[cfg(feature = "hot-reload")]
+impl ShaderUtils {
+    /// Loads the built-in modules from this crate's `shaders/`
+    /// directory through the `AssetServer`, so they hot-reload
+    /// with `watch_for_changes`. Each file declares its own
+    /// `#define_import_path`. Does nothing when the assets don't
+    /// come from the filesystem. The generated `fbm_*` modules
+    /// have no file, and are registered as usual.
+    fn load_from_disk(&mut self, world: &World) {
+        use bevy::asset::FileAssetIo;
+        use std::path::Path;
+
+        let asset_server = world.resource::<AssetServer>();
+        let Some(asset_io) = asset_server
+            .asset_io()
+            .downcast_ref::<FileAssetIo>()
+        else {
+            return;
+        };
+        // the watcher only reloads paths under the asset
+        // root, so the directory is reached relative to it
+        let shaders_dir = relative_path(
+            asset_io.root_path(),
+            &Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("shaders"),
+        );
+        for (name, source) in MODULES {
+            let path =
+                shaders_dir.join(format!("{}.wgsl", name));
+            self.modules.push(WgslModule {
+                import_path: format!(
+                    "{}::{}",
+                    NAMESPACE, name
+                ),
+                handle: asset_server.load(path),
+                source: Cow::Borrowed(*source),
+            });
         }
     }
 }
 
-fn load_shader(
-    shaders: &mut Mut<Assets<Shader>>,
-    name: &str,
-    shader_str: &'static str,
-) -> HandleId {
-    let mut shader = Shader::from_wgsl(shader_str);
-    shader.set_import_path(format!(
-        "bevy_shader_utils::{}",
-        name
-    ));
-    let id = HandleId::random::<Shader>();
-    shaders.set_untracked(id, shader);
-    id
+/// Puts the overrides of modules loaded from disk back when the
+/// asset server reloads their file. Runs after the asset events
+/// are sent and before the render world extracts the shaders, so
+/// the file's version never gets compiled.
+#[cfg(feature = "hot-reload")]
+fn keep_overrides(
+    mut events: EventReader<AssetEvent<Shader>>,
+    mut shader_utils: ResMut<ShaderUtils>,
+    mut shaders: ResMut<Assets<Shader>>,
+) {
+    let ShaderUtils { modules, overrides } =
+        &mut *shader_utils;
+    for event in events.iter() {
+        let (AssetEvent::Created { handle }
+        | AssetEvent::Modified { handle }) = event
+        else {
+            continue;
+        };
+        let Some(pending) = overrides.get_mut(&handle.id())
+        else {
+            continue;
+        };
+        // the override being set, not the file
+        if *pending > 0 {
+            *pending -= 1;
+            continue;
+        }
+        let module = modules
+            .iter()
+            .find(|module| module.handle == *handle)
+            .unwrap();
+        shaders.set_untracked(
+            handle.clone_weak(),
+            module_shader(
+                &module.import_path,
+                module.source.clone(),
+            ),
+        );
+        *pending += 1;
+    }
+}
+
+/// `to` as seen from `from`, going up with `..` as needed
+#[cfg(feature = "hot-reload")]
+fn relative_path(
+    from: &std::path::Path,
+    to: &std::path::Path,
+) -> std::path::PathBuf {
+    let from: Vec<_> = from.components().collect();
+    let to: Vec<_> = to.components().collect();
+    let common = from
+        .iter()
+        .zip(&to)
+        .take_while(|(a, b)| a == b)
+        .count();
+    std::iter::repeat(std::path::Component::ParentDir)
+        .take(from.len() - common)
+        .chain(to[common..].iter().copied())
+        .collect()
 }
@@ -0,0 +1,362 @@
+//! Rust ports of the noise modules, for sampling the same noise
+//! field on the CPU that the shaders render. They follow the WGSL
+//! step by step, so results only differ by the GPU's float precision.
+
+// the constants are kept exactly as the WGSL spells them
+#![allow(clippy::excessive_precision)]
+
+use bevy::math::{
+    Vec2, Vec2Swizzles, Vec3, Vec3Swizzles, Vec4,
+    Vec4Swizzles,
+};
+
+/// `perlinNoise2` from `bevy_shader_utils::perlin_noise_2d`
+pub fn perlin_noise_2d(p: Vec2) -> f32 {
+    let mut pi =
+        p.xyxy().floor() + Vec4::new(0., 0., 1., 1.);
+    let pf = p.xyxy().fract() - Vec4::new(0., 0., 1., 1.);
+    // To avoid truncation effects in permutation
+    pi %= 289.;
+    let ix = pi.xzxz();
+    let iy = pi.yyww();
+    let fx = pf.xzxz();
+    let fy = pf.yyww();
+    let i = permute4(permute4(ix) + iy);
+    let mut gx = 2. * (i * 0.0243902439).fract() - 1.;
+    let gy = gx.abs() - 0.5;
+    let tx = (gx + 0.5).floor();
+    gx -= tx;
+    let mut g00 = Vec2::new(gx.x, gy.x);
+    let mut g10 = Vec2::new(gx.y, gy.y);
+    let mut g01 = Vec2::new(gx.z, gy.z);
+    let mut g11 = Vec2::new(gx.w, gy.w);
+    let norm = 1.79284291400159
+        - 0.85373472095314
+            * Vec4::new(
+                g00.dot(g00),
+                g01.dot(g01),
+                g10.dot(g10),
+                g11.dot(g11),
+            );
+    g00 *= norm.x;
+    g01 *= norm.y;
+    g10 *= norm.z;
+    g11 *= norm.w;
+    let n00 = g00.dot(Vec2::new(fx.x, fy.x));
+    let n10 = g10.dot(Vec2::new(fx.y, fy.y));
+    let n01 = g01.dot(Vec2::new(fx.z, fy.z));
+    let n11 = g11.dot(Vec2::new(fx.w, fy.w));
+    let fade_xy = fade2(pf.xy());
+    let n_x = mix2(
+        Vec2::new(n00, n01),
+        Vec2::new(n10, n11),
+        Vec2::splat(fade_xy.x),
+    );
+    let n_xy = mix(n_x.x, n_x.y, fade_xy.y);
+    2.3 * n_xy
+}
+
+/// `perlinNoise3` from `bevy_shader_utils::perlin_noise_3d`
+pub fn perlin_noise_3d(p: Vec3) -> f32 {
+    // Integer part for indexing
+    let mut pi0 = p.floor();
+    // Integer part + 1
+    let mut pi1 = pi0 + 1.;
+    pi0 %= 289.;
+    pi1 %= 289.;
+    // Fractional part for interpolation
+    let pf0 = p.fract();
+    // Fractional part - 1.
+    let pf1 = pf0 - 1.;
+    let ix = Vec4::new(pi0.x, pi1.x, pi0.x, pi1.x);
+    let iy = Vec4::new(pi0.y, pi0.y, pi1.y, pi1.y);
+    let iz0 = Vec4::splat(pi0.z);
+    let iz1 = Vec4::splat(pi1.z);
+
+    let ixy = permute4(permute4(ix) + iy);
+    let ixy0 = permute4(ixy + iz0);
+    let ixy1 = permute4(ixy + iz1);
+
+    let mut gx0 = ixy0 / 7.;
+    let mut gy0 = (gx0.floor() / 7.).fract() - 0.5;
+    gx0 = gx0.fract();
+    let gz0 = Vec4::splat(0.5) - gx0.abs() - gy0.abs();
+    let sz0 = step4(gz0, Vec4::ZERO);
+    gx0 -= sz0 * (step4(Vec4::ZERO, gx0) - 0.5);
+    gy0 -= sz0 * (step4(Vec4::ZERO, gy0) - 0.5);
+
+    let mut gx1 = ixy1 / 7.;
+    let mut gy1 = (gx1.floor() / 7.).fract() - 0.5;
+    gx1 = gx1.fract();
+    let gz1 = Vec4::splat(0.5) - gx1.abs() - gy1.abs();
+    let sz1 = step4(gz1, Vec4::ZERO);
+    gx1 -= sz1 * (step4(Vec4::ZERO, gx1) - 0.5);
+    gy1 -= sz1 * (step4(Vec4::ZERO, gy1) - 0.5);
+
+    let mut g000 = Vec3::new(gx0.x, gy0.x, gz0.x);
+    let mut g100 = Vec3::new(gx0.y, gy0.y, gz0.y);
+    let mut g010 = Vec3::new(gx0.z, gy0.z, gz0.z);
+    let mut g110 = Vec3::new(gx0.w, gy0.w, gz0.w);
+    let mut g001 = Vec3::new(gx1.x, gy1.x, gz1.x);
+    let mut g101 = Vec3::new(gx1.y, gy1.y, gz1.y);
+    let mut g011 = Vec3::new(gx1.z, gy1.z, gz1.z);
+    let mut g111 = Vec3::new(gx1.w, gy1.w, gz1.w);
+
+    let norm0 = taylor_inv_sqrt4(Vec4::new(
+        g000.dot(g000),
+        g010.dot(g010),
+        g100.dot(g100),
+        g110.dot(g110),
+    ));
+    g000 *= norm0.x;
+    g010 *= norm0.y;
+    g100 *= norm0.z;
+    g110 *= norm0.w;
+    let norm1 = taylor_inv_sqrt4(Vec4::new(
+        g001.dot(g001),
+        g011.dot(g011),
+        g101.dot(g101),
+        g111.dot(g111),
+    ));
+    g001 *= norm1.x;
+    g011 *= norm1.y;
+    g101 *= norm1.z;
+    g111 *= norm1.w;
+
+    let n000 = g000.dot(pf0);
+    let n100 = g100.dot(Vec3::new(pf1.x, pf0.y, pf0.z));
+    let n010 = g010.dot(Vec3::new(pf0.x, pf1.y, pf0.z));
+    let n110 = g110.dot(Vec3::new(pf1.x, pf1.y, pf0.z));
+    let n001 = g001.dot(Vec3::new(pf0.x, pf0.y, pf1.z));
+    let n101 = g101.dot(Vec3::new(pf1.x, pf0.y, pf1.z));
+    let n011 = g011.dot(Vec3::new(pf0.x, pf1.y, pf1.z));
+    let n111 = g111.dot(pf1);
+
+    let fade_xyz = fade3(pf0);
+    let n_z = mix4(
+        Vec4::new(n000, n100, n010, n110),
+        Vec4::new(n001, n101, n011, n111),
+        Vec4::splat(fade_xyz.z),
+    );
+    let n_yz =
+        mix2(n_z.xy(), n_z.zw(), Vec2::splat(fade_xyz.y));
+    let n_xyz = mix(n_yz.x, n_yz.y, fade_xyz.x);
+    2.2 * n_xyz
+}
+
+/// `simplexNoise2` from `bevy_shader_utils::simplex_noise_2d`
+pub fn simplex_noise_2d(v: Vec2) -> f32 {
+    let c = Vec4::new(
+        0.211324865405187,
+        0.366025403784439,
+        -0.577350269189626,
+        0.024390243902439,
+    );
+    let mut i = (v + v.dot(c.yy())).floor();
+    let x0 = v - i + i.dot(c.xx());
+    // the middle corner is a step along the larger offset
+    let i1 = if x0.x > x0.y {
+        Vec2::new(1., 0.)
+    } else {
+        Vec2::new(0., 1.)
+    };
+    let x12 = x0.xyxy() + c.xxzz()
+        - Vec4::new(i1.x, i1.y, 0., 0.);
+    i %= 289.;
+    let p = permute3(
+        permute3(i.y + Vec3::new(0., i1.y, 1.))
+            + i.x
+            + Vec3::new(0., i1.x, 1.),
+    );
+    let mut m = (0.5
+        - Vec3::new(
+            x0.dot(x0),
+            x12.xy().dot(x12.xy()),
+            x12.zw().dot(x12.zw()),
+        ))
+    .max(Vec3::ZERO);
+    m *= m;
+    m *= m;
+    let x = 2. * (p * c.www()).fract() - 1.;
+    let h = x.abs() - 0.5;
+    let ox = (x + 0.5).floor();
+    let a0 = x - ox;
+    m *= 1.79284291400159
+        - 0.85373472095314 * (a0 * a0 + h * h);
+    let g_yz = a0.yz() * x12.xz() + h.yz() * x12.yw();
+    let g =
+        Vec3::new(a0.x * x0.x + h.x * x0.y, g_yz.x, g_yz.y);
+    130. * m.dot(g)
+}
+
+/// `simplexNoise3` from `bevy_shader_utils::simplex_noise_3d`
+pub fn simplex_noise_3d(v: Vec3) -> f32 {
+    let c = Vec2::new(1. / 6., 1. / 3.);
+    let d = Vec4::new(0., 0.5, 1., 2.);
+
+    // First corner
+    let mut i = (v + v.dot(c.yyy())).floor();
+    let x0 = v - i + i.dot(c.xxx());
+
+    // Other corners
+    let g = step3(x0.yzx(), x0);
+    let l = 1. - g;
+    let i1 = g.min(l.zxy());
+    let i2 = g.max(l.zxy());
+
+    let x1 = x0 - i1 + 1. * c.xxx();
+    let x2 = x0 - i2 + 2. * c.xxx();
+    let x3 = x0 - 1. + 3. * c.xxx();
+
+    // Permutations
+    i %= 289.;
+    let p = permute4(
+        permute4(
+            permute4(i.z + Vec4::new(0., i1.z, i2.z, 1.))
+                + i.y
+                + Vec4::new(0., i1.y, i2.y, 1.),
+        ) + i.x
+            + Vec4::new(0., i1.x, i2.x, 1.),
+    );
+
+    // Gradients (NxN points uniformly over a square, mapped onto
+    // an octahedron.)
+    let n_ = 1. / 7.; // N=7
+    let ns = n_ * d.wyz() - d.xzx();
+
+    let j = p - 49. * (p * ns.z * ns.z).floor(); // mod(p, N*N)
+
+    let x_ = (j * ns.z).floor();
+    let y_ = (j - 7. * x_).floor(); // mod(j, N)
+
+    let x = x_ * ns.x + Vec4::splat(ns.y);
+    let y = y_ * ns.x + Vec4::splat(ns.y);
+    let h = 1. - x.abs() - y.abs();
+
+    let b0 = Vec4::new(x.x, x.y, y.x, y.y);
+    let b1 = Vec4::new(x.z, x.w, y.z, y.w);
+
+    let s0 = b0.floor() * 2. + 1.;
+    let s1 = b1.floor() * 2. + 1.;
+    let sh = -step4(h, Vec4::ZERO);
+
+    let a0 = b0.xzyw() + s0.xzyw() * sh.xxyy();
+    let a1 = b1.xzyw() + s1.xzyw() * sh.zzww();
+
+    let mut p0 = a0.xy().extend(h.x);
+    let mut p1 = a0.zw().extend(h.y);
+    let mut p2 = a1.xy().extend(h.z);
+    let mut p3 = a1.zw().extend(h.w);
+
+    // Normalise gradients
+    let norm = taylor_inv_sqrt4(Vec4::new(
+        p0.dot(p0),
+        p1.dot(p1),
+        p2.dot(p2),
+        p3.dot(p3),
+    ));
+    p0 *= norm.x;
+    p1 *= norm.y;
+    p2 *= norm.z;
+    p3 *= norm.w;
+
+    // Mix final noise value
+    let mut m = 0.6
+        - Vec4::new(
+            x0.dot(x0),
+            x1.dot(x1),
+            x2.dot(x2),
+            x3.dot(x3),
+        );
+    m = m.max(Vec4::ZERO);
+    m *= m;
+    42. * (m * m).dot(Vec4::new(
+        p0.dot(x0),
+        p1.dot(x1),
+        p2.dot(x2),
+        p3.dot(x3),
+    ))
+}
+
+/// `voronoise` from `bevy_shader_utils::voronoise`, `u` blends from
+/// a regular grid to jittered cells and `v` from hard cells to
+/// smooth noise
+pub fn voronoise(p: Vec2, u: f32, v: f32) -> f32 {
+    let k = 1. + 63. * (1. - v).powf(6.);
+
+    let i = p.floor();
+    let f = p.fract();
+
+    let mut a = Vec2::ZERO;
+    for y in -2..=2 {
+        for x in -2..=2 {
+            let g = Vec2::new(x as f32, y as f32);
+            let o = hash3(i + g) * Vec3::new(u, u, 1.);
+            let d = g - f + o.xy();
+            let w = (1.
+                - smoothstep(0., 1.414, d.length()))
+            .powf(k);
+            a += Vec2::new(o.z * w, w);
+        }
+    }
+
+    a.x / a.y
+}
+
+fn hash3(p: Vec2) -> Vec3 {
+    let q = Vec3::new(
+        p.dot(Vec2::new(127.1, 311.7)),
+        p.dot(Vec2::new(269.5, 183.3)),
+        p.dot(Vec2::new(419.2, 371.9)),
+    );
+    (Vec3::new(q.x.sin(), q.y.sin(), q.z.sin())
+        * 43758.5453)
+        .fract()
+}
+
+fn permute3(x: Vec3) -> Vec3 {
+    ((x * 34. + 1.) * x) % 289.
+}
+
+fn permute4(x: Vec4) -> Vec4 {
+    ((x * 34. + 1.) * x) % 289.
+}
+
+fn taylor_inv_sqrt4(r: Vec4) -> Vec4 {
+    1.79284291400159 - 0.85373472095314 * r
+}
+
+fn fade2(t: Vec2) -> Vec2 {
+    t * t * t * (t * (t * 6. - 15.) + 10.)
+}
+
+fn fade3(t: Vec3) -> Vec3 {
+    t * t * t * (t * (t * 6. - 15.) + 10.)
+}
+
+// WGSL's built-ins, where glam's differ or are missing
+
+fn mix(x: f32, y: f32, a: f32) -> f32 {
+    x * (1. - a) + y * a
+}
+
+fn mix2(x: Vec2, y: Vec2, a: Vec2) -> Vec2 {
+    x * (1. - a) + y * a
+}
+
+fn mix4(x: Vec4, y: Vec4, a: Vec4) -> Vec4 {
+    x * (1. - a) + y * a
+}
+
+fn step3(edge: Vec3, x: Vec3) -> Vec3 {
+    Vec3::select(x.cmpge(edge), Vec3::ONE, Vec3::ZERO)
+}
+
+fn step4(edge: Vec4, x: Vec4) -> Vec4 {
+    Vec4::select(x.cmpge(edge), Vec4::ONE, Vec4::ZERO)
+}
+
+fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+    let t = ((x - edge0) / (edge1 - edge0)).clamp(0., 1.);
+    t * t * (3. - 2. * t)
+}
@@ -0,0 +1,210 @@
+mod common;
+
+use bevy::math::Vec3;
+use bevy_shader_utils::*;
+use common::{interpreter::*, *};
+
+/// How far the Rust ports may stray from the WGSL run on the CPU,
+/// relative to values above 1
+const TOLERANCE: f32 = 1e-5;
+
+/// A port taking a color to a color, by its shader name
+type Conversion = (&'static str, fn(Vec3) -> Vec3);
+
+/// Colors through the unit cube, grays and the corners included
+fn colors() -> impl Iterator<Item = Vec3> {
+    let steps = [0., 0.02, 0.2, 0.5, 0.73, 1.];
+    steps.into_iter().flat_map(move |r| {
+        steps.into_iter().flat_map(move |g| {
+            steps
+                .into_iter()
+                .map(move |b| Vec3::new(r, g, b))
+        })
+    })
+}
+
+fn assert_close(actual: Vec3, expected: Vec3, what: &str) {
+    let tolerance =
+        TOLERANCE * expected.abs().max_element().max(1.);
+    assert!(
+        (actual - expected).abs().max_element()
+            <= tolerance,
+        "{what}: got {actual}, expected {expected}",
+    );
+}
+
+fn assert_parity(
+    shader: &Interpreter,
+    name: &str,
+    port: fn(Vec3) -> Vec3,
+    samples: impl Iterator<Item = Vec3>,
+) {
+    for c in samples {
+        let actual = Vec3::from_slice(
+            &shader.call(name, &[c.into()]).f32s(),
+        );
+        assert_close(
+            actual,
+            port(c),
+            &format!("{name}({c})"),
+        );
+    }
+}
+
+#[test]
+fn color_space_matches_the_shader() {
+    let shader =
+        shader(&["bevy_shader_utils::color_space"]);
+    let conversions: [Conversion; 8] = [
+        ("srgbToLinear", srgb_to_linear),
+        ("linearToSrgb", linear_to_srgb),
+        ("rgbToHsv", rgb_to_hsv),
+        ("hsvToRgb", hsv_to_rgb),
+        ("rgbToHsl", rgb_to_hsl),
+        ("hslToRgb", hsl_to_rgb),
+        ("linearToOklab", linear_to_oklab),
+        ("oklabToLinear", oklab_to_linear),
+    ];
+    for (name, port) in conversions {
+        assert_parity(&shader, name, port, colors());
+    }
+    for c in colors() {
+        let actual = shader
+            .call("relativeLuminance", &[c.into()])
+            .f32();
+        assert!(
+            (actual - relative_luminance(c)).abs()
+                <= TOLERANCE
+        );
+    }
+}
+
+#[test]
+fn palette_matches_the_shader() {
+    let shader = shader(&["bevy_shader_utils::palette"]);
+    let (a, b, c, d) = (
+        Vec3::new(0.5, 0.5, 0.5),
+        Vec3::new(0.5, 0.5, 0.5),
+        Vec3::new(1., 0.7, 0.4),
+        Vec3::new(0., 0.15, 0.2),
+    );
+    for t in (0..=20).map(|t| t as f32 * 0.05) {
+        let actual = shader.call(
+            "cosinePalette",
+            &[
+                t.into(),
+                a.into(),
+                b.into(),
+                c.into(),
+                d.into(),
+            ],
+        );
+        assert_close(
+            Vec3::from_slice(&actual.f32s()),
+            cosine_palette(t, a, b, c, d),
+            &format!("cosinePalette({t})"),
+        );
+        let actual =
+            shader.call("rainbowPalette", &[t.into()]);
+        assert_close(
+            Vec3::from_slice(&actual.f32s()),
+            rainbow_palette(t),
+            &format!("rainbowPalette({t})"),
+        );
+    }
+}
+
+#[test]
+fn tonemapping_matches_the_shader() {
+    let shader =
+        shader(&["bevy_shader_utils::tonemapping"]);
+    // HDR colors, well past 1
+    let hdr = || colors().map(|c| c * 8.);
+    let curves: [Conversion; 3] = [
+        ("tonemapReinhard", tonemap_reinhard),
+        (
+            "tonemapReinhardLuminance",
+            tonemap_reinhard_luminance,
+        ),
+        ("tonemapAcesFilmic", tonemap_aces_filmic),
+    ];
+    for (name, port) in curves {
+        assert_parity(&shader, name, port, hdr());
+    }
+    for c in hdr() {
+        let actual = shader.call(
+            "tonemapReinhardExtended",
+            &[c.into(), 4.0.into()],
+        );
+        assert_close(
+            Vec3::from_slice(&actual.f32s()),
+            tonemap_reinhard_extended(c, 4.),
+            &format!("tonemapReinhardExtended({c})"),
+        );
+    }
+}
+
+#[test]
+fn conversions_round_trip() {
+    let round_trips: [Conversion; 4] = [
+        ("sRGB", |c| linear_to_srgb(srgb_to_linear(c))),
+        ("HSV", |c| hsv_to_rgb(rgb_to_hsv(c))),
+        ("HSL", |c| hsl_to_rgb(rgb_to_hsl(c))),
+        ("Oklab", |c| oklab_to_linear(linear_to_oklab(c))),
+    ];
+    for (name, round_trip) in round_trips {
+        for c in colors() {
+            let back = round_trip(c);
+            assert!(
+                (back - c).abs().max_element() < 1e-4,
+                "{c} comes back from {name} as {back}",
+            );
+        }
+    }
+}
+
+#[test]
+fn conversions_give_known_values() {
+    let close = |actual: Vec3, expected: Vec3| {
+        (actual - expected).abs().max_element() < 1e-4
+    };
+    assert!(close(
+        srgb_to_linear(Vec3::splat(0.5)),
+        Vec3::splat(0.214041),
+    ));
+    // white is L = 1 with no color, and the hues go
+    // around in turns
+    assert!(close(
+        linear_to_oklab(Vec3::ONE),
+        Vec3::new(1., 0., 0.),
+    ));
+    assert!(close(
+        rgb_to_hsv(Vec3::new(0., 1., 0.)),
+        Vec3::new(1. / 3., 1., 1.),
+    ));
+    assert!(close(
+        rgb_to_hsl(Vec3::new(0., 0., 0.5)),
+        Vec3::new(2. / 3., 1., 0.25),
+    ));
+    assert!(close(
+        hsv_to_rgb(Vec3::new(1. / 6., 1., 1.)),
+        Vec3::new(1., 1., 0.),
+    ));
+    assert!(close(
+        hsl_to_rgb(Vec3::new(0.5, 0., 0.3)),
+        Vec3::splat(0.3),
+    ));
+    // extended Reinhard reaches 1 at white, ACES clamps there
+    assert!(close(
+        tonemap_reinhard_extended(Vec3::splat(4.), 4.),
+        Vec3::ONE,
+    ));
+    assert!(close(
+        tonemap_aces_filmic(Vec3::splat(1000.)),
+        Vec3::ONE,
+    ));
+    assert!(close(
+        tonemap_reinhard(Vec3::new(1., 3., 0.)),
+        Vec3::new(0.5, 0.75, 0.),
+    ));
+}
@@ -0,0 +1,818 @@
+//! Runs WGSL functions on the CPU by walking naga's IR, enough of
+//! it for the math the noise modules do, so the Rust ports can be
+//! checked against the shaders without a GPU.
+
+use bevy::math::{Vec2, Vec3, Vec4};
+use naga::{
+    BinaryOperator, Block, ConstantInner, Expression,
+    Function, Handle, MathFunction, Module, ScalarKind,
+    ScalarValue, Statement, TypeInner, UnaryOperator,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    F32(f32),
+    I32(i32),
+    U32(u32),
+    Bool(bool),
+    /// Vectors, and matrices as vectors of columns
+    Composite(Vec<Value>),
+    Pointer(Handle<naga::LocalVariable>, Vec<usize>),
+}
+
+impl Value {
+    pub fn f32(&self) -> f32 {
+        match self {
+            Value::F32(value) => *value,
+            value => {
+                panic!("expected an f32, got {value:?}")
+            }
+        }
+    }
+
+    /// The components of a vector of floats
+    pub fn f32s(&self) -> Vec<f32> {
+        self.components().iter().map(Value::f32).collect()
+    }
+
+    fn bool(&self) -> bool {
+        match self {
+            Value::Bool(value) => *value,
+            value => {
+                panic!("expected a bool, got {value:?}")
+            }
+        }
+    }
+
+    fn components(&self) -> &[Value] {
+        match self {
+            Value::Composite(components) => components,
+            value => {
+                panic!("expected a vector, got {value:?}")
+            }
+        }
+    }
+
+    fn map(&self, f: &impl Fn(&Value) -> Value) -> Value {
+        match self {
+            Value::Composite(components) => {
+                Value::Composite(
+                    components
+                        .iter()
+                        .map(|c| c.map(f))
+                        .collect(),
+                )
+            }
+            scalar => f(scalar),
+        }
+    }
+
+    /// Applies `f` component wise, splatting scalars
+    fn zip(
+        &self,
+        other: &Value,
+        f: &impl Fn(&Value, &Value) -> Value,
+    ) -> Value {
+        match (self, other) {
+            (Value::Composite(a), Value::Composite(b)) => {
+                Value::Composite(
+                    a.iter()
+                        .zip(b)
+                        .map(|(a, b)| a.zip(b, f))
+                        .collect(),
+                )
+            }
+            (Value::Composite(a), b) => Value::Composite(
+                a.iter().map(|a| a.zip(b, f)).collect(),
+            ),
+            (a, Value::Composite(b)) => Value::Composite(
+                b.iter().map(|b| a.zip(b, f)).collect(),
+            ),
+            (a, b) => f(a, b),
+        }
+    }
+
+    fn zip3(
+        &self,
+        b: &Value,
+        c: &Value,
+        f: &impl Fn(f32, f32, f32) -> f32,
+    ) -> Value {
+        match (self, b, c) {
+            (
+                Value::F32(a),
+                Value::F32(b),
+                Value::F32(c),
+            ) => Value::F32(f(*a, *b, *c)),
+            _ => {
+                let len = [self, b, c]
+                    .iter()
+                    .find_map(|value| match value {
+                        Value::Composite(c) => {
+                            Some(c.len())
+                        }
+                        _ => None,
+                    })
+                    .unwrap();
+                let component =
+                    |value: &Value, i: usize| match value {
+                        Value::Composite(c) => c[i].clone(),
+                        scalar => scalar.clone(),
+                    };
+                Value::Composite(
+                    (0..len)
+                        .map(|i| {
+                            component(self, i).zip3(
+                                &component(b, i),
+                                &component(c, i),
+                                f,
+                            )
+                        })
+                        .collect(),
+                )
+            }
+        }
+    }
+
+    fn is_matrix(&self) -> bool {
+        matches!(self, Value::Composite(columns)
+            if matches!(columns[0], Value::Composite(_)))
+    }
+}
+
+impl From<f32> for Value {
+    fn from(value: f32) -> Self {
+        Value::F32(value)
+    }
+}
+
+impl From<Vec2> for Value {
+    fn from(value: Vec2) -> Self {
+        Value::Composite(
+            value.to_array().map(Value::F32).to_vec(),
+        )
+    }
+}
+
+impl From<Vec3> for Value {
+    fn from(value: Vec3) -> Self {
+        Value::Composite(
+            value.to_array().map(Value::F32).to_vec(),
+        )
+    }
+}
+
+impl From<Vec4> for Value {
+    fn from(value: Vec4) -> Self {
+        Value::Composite(
+            value.to_array().map(Value::F32).to_vec(),
+        )
+    }
+}
+
+enum Flow {
+    Next,
+    Break,
+    Continue,
+    Return(Option<Value>),
+}
+
+struct Frame<'a> {
+    function: &'a Function,
+    arguments: Vec<Value>,
+    expressions: Vec<Option<Value>>,
+    locals: Vec<Value>,
+}
+
+pub struct Interpreter {
+    module: Module,
+}
+
+impl Interpreter {
+    pub fn new(module: Module) -> Self {
+        Interpreter { module }
+    }
+
+    /// Calls the function named `name`
+    pub fn call(
+        &self,
+        name: &str,
+        arguments: &[Value],
+    ) -> Value {
+        let (handle, _) = self
+            .module
+            .functions
+            .iter()
+            .find(|(_, function)| {
+                function.name.as_deref() == Some(name)
+            })
+            .unwrap_or_else(|| {
+                panic!("no function {name}")
+            });
+        self.run(handle, arguments.to_vec())
+            .expect("the function returns nothing")
+    }
+
+    fn run(
+        &self,
+        function: Handle<Function>,
+        arguments: Vec<Value>,
+    ) -> Option<Value> {
+        let function = &self.module.functions[function];
+        let mut frame = Frame {
+            function,
+            arguments,
+            expressions: vec![
+                None;
+                function.expressions.len()
+            ],
+            locals: function
+                .local_variables
+                .iter()
+                .map(|(_, local)| match local.init {
+                    Some(init) => self.constant(init),
+                    None => self.zero(local.ty),
+                })
+                .collect(),
+        };
+        match self.block(&mut frame, &function.body) {
+            Flow::Return(value) => value,
+            _ => None,
+        }
+    }
+
+    fn block(
+        &self,
+        frame: &mut Frame,
+        block: &Block,
+    ) -> Flow {
+        for statement in block.iter() {
+            let flow = self.statement(frame, statement);
+            if !matches!(flow, Flow::Next) {
+                return flow;
+            }
+        }
+        Flow::Next
+    }
+
+    fn statement(
+        &self,
+        frame: &mut Frame,
+        statement: &Statement,
+    ) -> Flow {
+        match statement {
+            Statement::Emit(range) => {
+                for handle in range.clone() {
+                    let value =
+                        self.evaluate(frame, handle);
+                    frame.expressions[handle.index()] =
+                        Some(value);
+                }
+            }
+            Statement::Block(block) => {
+                return self.block(frame, block)
+            }
+            Statement::If {
+                condition,
+                accept,
+                reject,
+            } => {
+                let block =
+                    if self.get(frame, *condition).bool() {
+                        accept
+                    } else {
+                        reject
+                    };
+                return self.block(frame, block);
+            }
+            Statement::Loop {
+                body,
+                continuing,
+                break_if,
+            } => loop {
+                match self.block(frame, body) {
+                    Flow::Break => break,
+                    Flow::Return(value) => {
+                        return Flow::Return(value)
+                    }
+                    Flow::Next | Flow::Continue => {}
+                }
+                self.block(frame, continuing);
+                if let Some(break_if) = break_if {
+                    if self.get(frame, *break_if).bool() {
+                        break;
+                    }
+                }
+            },
+            Statement::Break => return Flow::Break,
+            Statement::Continue => return Flow::Continue,
+            Statement::Return { value } => {
+                return Flow::Return(
+                    value.map(|value| {
+                        self.get(frame, value)
+                    }),
+                )
+            }
+            Statement::Store { pointer, value } => {
+                let value = self.get(frame, *value);
+                let Value::Pointer(local, path) =
+                    self.get(frame, *pointer)
+                else {
+                    panic!("can only store to locals");
+                };
+                let mut target =
+                    &mut frame.locals[local.index()];
+                for index in path {
+                    let Value::Composite(components) =
+                        target
+                    else {
+                        panic!("indexing a scalar");
+                    };
+                    target = &mut components[index];
+                }
+                *target = value;
+            }
+            Statement::Call {
+                function,
+                arguments,
+                result,
+            } => {
+                let arguments = arguments
+                    .iter()
+                    .map(|argument| {
+                        self.get(frame, *argument)
+                    })
+                    .collect();
+                let value = self.run(*function, arguments);
+                if let Some(result) = result {
+                    frame.expressions[result.index()] =
+                        value;
+                }
+            }
+            statement => {
+                panic!(
+                    "unsupported statement {statement:?}"
+                )
+            }
+        }
+        Flow::Next
+    }
+
+    /// The value of an expression that was emitted already, or
+    /// one that needs no emitting, like constants
+    fn get(
+        &self,
+        frame: &mut Frame,
+        handle: Handle<Expression>,
+    ) -> Value {
+        if let Some(value) =
+            &frame.expressions[handle.index()]
+        {
+            return value.clone();
+        }
+        let value = self.evaluate(frame, handle);
+        frame.expressions[handle.index()] =
+            Some(value.clone());
+        value
+    }
+
+    fn evaluate(
+        &self,
+        frame: &mut Frame,
+        handle: Handle<Expression>,
+    ) -> Value {
+        let function = frame.function;
+        match &function.expressions[handle] {
+            Expression::Constant(constant) => {
+                self.constant(*constant)
+            }
+            Expression::FunctionArgument(index) => {
+                frame.arguments[*index as usize].clone()
+            }
+            Expression::LocalVariable(local) => {
+                Value::Pointer(*local, Vec::new())
+            }
+            Expression::Load { pointer } => {
+                let Value::Pointer(local, path) =
+                    self.get(frame, *pointer)
+                else {
+                    panic!("can only load from locals");
+                };
+                let mut value =
+                    &frame.locals[local.index()];
+                for index in path {
+                    value = &value.components()[index];
+                }
+                value.clone()
+            }
+            Expression::Access { base, index } => {
+                let index = match self.get(frame, *index) {
+                    Value::I32(index) => index as usize,
+                    Value::U32(index) => index as usize,
+                    value => panic!("bad index {value:?}"),
+                };
+                self.access(frame, *base, index)
+            }
+            Expression::AccessIndex { base, index } => {
+                self.access(frame, *base, *index as usize)
+            }
+            Expression::Splat { size, value } => {
+                Value::Composite(vec![
+                    self.get(
+                        frame, *value
+                    );
+                    *size as usize
+                ])
+            }
+            Expression::Swizzle {
+                size,
+                vector,
+                pattern,
+            } => {
+                let vector = self.get(frame, *vector);
+                Value::Composite(
+                    pattern[..*size as usize]
+                        .iter()
+                        .map(|component| {
+                            vector.components()
+                                [*component as usize]
+                                .clone()
+                        })
+                        .collect(),
+                )
+            }
+            Expression::Compose { ty, components } => {
+                let components: Vec<_> = components
+                    .iter()
+                    .map(|component| {
+                        self.get(frame, *component)
+                    })
+                    .collect();
+                match self.module.types[*ty].inner {
+                    // vec4(v.xy, z, w) flattens
+                    TypeInner::Vector { .. } => {
+                        Value::Composite(
+                            components
+                                .into_iter()
+                                .flat_map(|component| {
+                                    match component
+                            {
+                                Value::Composite(c) => c,
+                                scalar => vec![scalar],
+                            }
+                                })
+                                .collect(),
+                        )
+                    }
+                    _ => Value::Composite(components),
+                }
+            }
+            Expression::Unary { op, expr } => {
+                let value = self.get(frame, *expr);
+                match op {
+                    UnaryOperator::Negate => {
+                        value.map(&|value| match value {
+                            Value::F32(v) => Value::F32(-v),
+                            Value::I32(v) => Value::I32(-v),
+                            value => panic!(
+                                "can't negate {value:?}"
+                            ),
+                        })
+                    }
+                    UnaryOperator::Not => {
+                        value.map(&|value| match value {
+                            Value::Bool(v) => {
+                                Value::Bool(!v)
+                            }
+                            value => panic!(
+                                "can't invert {value:?}"
+                            ),
+                        })
+                    }
+                }
+            }
+            Expression::Binary { op, left, right } => {
+                let left = self.get(frame, *left);
+                let right = self.get(frame, *right);
+                binary(*op, &left, &right)
+            }
+            Expression::Select {
+                condition,
+                accept,
+                reject,
+            } => {
+                if self.get(frame, *condition).bool() {
+                    self.get(frame, *accept)
+                } else {
+                    self.get(frame, *reject)
+                }
+            }
+            Expression::Math {
+                fun,
+                arg,
+                arg1,
+                arg2,
+                ..
+            } => {
+                let arg = self.get(frame, *arg);
+                let arg1 =
+                    arg1.map(|arg| self.get(frame, arg));
+                let arg2 =
+                    arg2.map(|arg| self.get(frame, arg));
+                math(
+                    *fun,
+                    &arg,
+                    arg1.as_ref(),
+                    arg2.as_ref(),
+                )
+            }
+            Expression::As {
+                expr,
+                kind,
+                convert: Some(_),
+            } => {
+                let kind = *kind;
+                self.get(frame, *expr).map(&|value| {
+                    let value = match value {
+                        Value::F32(v) => *v as f64,
+                        Value::I32(v) => *v as f64,
+                        Value::U32(v) => *v as f64,
+                        Value::Bool(v) => *v as u8 as f64,
+                        value => panic!(
+                            "can't convert {value:?}"
+                        ),
+                    };
+                    match kind {
+                        ScalarKind::Float => {
+                            Value::F32(value as f32)
+                        }
+                        ScalarKind::Sint => {
+                            Value::I32(value as i32)
+                        }
+                        ScalarKind::Uint => {
+                            Value::U32(value as u32)
+                        }
+                        ScalarKind::Bool => {
+                            Value::Bool(value != 0.)
+                        }
+                    }
+                })
+            }
+            expression => {
+                panic!(
+                    "unsupported expression {expression:?}"
+                )
+            }
+        }
+    }
+
+    fn access(
+        &self,
+        frame: &mut Frame,
+        base: Handle<Expression>,
+        index: usize,
+    ) -> Value {
+        match self.get(frame, base) {
+            Value::Pointer(local, mut path) => {
+                path.push(index);
+                Value::Pointer(local, path)
+            }
+            value => value.components()[index].clone(),
+        }
+    }
+
+    fn constant(
+        &self,
+        constant: Handle<naga::Constant>,
+    ) -> Value {
+        match &self.module.constants[constant].inner {
+            ConstantInner::Scalar { value, .. } => {
+                match value {
+                    ScalarValue::Float(v) => {
+                        Value::F32(*v as f32)
+                    }
+                    ScalarValue::Sint(v) => {
+                        Value::I32(*v as i32)
+                    }
+                    ScalarValue::Uint(v) => {
+                        Value::U32(*v as u32)
+                    }
+                    ScalarValue::Bool(v) => Value::Bool(*v),
+                }
+            }
+            ConstantInner::Composite {
+                components, ..
+            } => Value::Composite(
+                components
+                    .iter()
+                    .map(|c| self.constant(*c))
+                    .collect(),
+            ),
+        }
+    }
+
+    fn zero(&self, ty: Handle<naga::Type>) -> Value {
+        let scalar = |kind| match kind {
+            ScalarKind::Float => Value::F32(0.),
+            ScalarKind::Sint => Value::I32(0),
+            ScalarKind::Uint => Value::U32(0),
+            ScalarKind::Bool => Value::Bool(false),
+        };
+        match self.module.types[ty].inner {
+            TypeInner::Scalar { kind, .. } => scalar(kind),
+            TypeInner::Vector { size, kind, .. } => {
+                Value::Composite(vec![
+                    scalar(kind);
+                    size as usize
+                ])
+            }
+            TypeInner::Matrix { columns, rows, .. } => {
+                Value::Composite(vec![
+                    Value::Composite(
+                        vec![Value::F32(0.); rows as usize]
+                    );
+                    columns as usize
+                ])
+            }
+            ref ty => panic!("unsupported type {ty:?}"),
+        }
+    }
+}
+
+fn binary(
+    op: BinaryOperator,
+    left: &Value,
+    right: &Value,
+) -> Value {
+    use BinaryOperator::*;
+    if op == Multiply
+        && left.is_matrix()
+        && !right.is_matrix()
+    {
+        if let Value::Composite(vector) = right {
+            // the columns weighted by the vector
+            return left
+                .components()
+                .iter()
+                .zip(vector)
+                .map(|(column, weight)| {
+                    binary(Multiply, column, weight)
+                })
+                .reduce(|a, b| binary(Add, &a, &b))
+                .unwrap();
+        }
+    }
+    left.zip(right, &|a, b| match (a, b) {
+        (Value::F32(a), Value::F32(b)) => match op {
+            Add => Value::F32(a + b),
+            Subtract => Value::F32(a - b),
+            Multiply => Value::F32(a * b),
+            Divide => Value::F32(a / b),
+            Modulo => Value::F32(a % b),
+            Equal => Value::Bool(a == b),
+            NotEqual => Value::Bool(a != b),
+            Less => Value::Bool(a < b),
+            LessEqual => Value::Bool(a <= b),
+            Greater => Value::Bool(a > b),
+            GreaterEqual => Value::Bool(a >= b),
+            op => panic!("unsupported f32 operator {op:?}"),
+        },
+        (Value::I32(a), Value::I32(b)) => match op {
+            Add => Value::I32(a.wrapping_add(*b)),
+            Subtract => Value::I32(a.wrapping_sub(*b)),
+            Multiply => Value::I32(a.wrapping_mul(*b)),
+            Divide => Value::I32(a / b),
+            Modulo => Value::I32(a % b),
+            Equal => Value::Bool(a == b),
+            NotEqual => Value::Bool(a != b),
+            Less => Value::Bool(a < b),
+            LessEqual => Value::Bool(a <= b),
+            Greater => Value::Bool(a > b),
+            GreaterEqual => Value::Bool(a >= b),
+            op => panic!("unsupported i32 operator {op:?}"),
+        },
+        (Value::Bool(a), Value::Bool(b)) => match op {
+            LogicalAnd | And => Value::Bool(*a && *b),
+            LogicalOr | InclusiveOr => {
+                Value::Bool(*a || *b)
+            }
+            Equal => Value::Bool(a == b),
+            NotEqual => Value::Bool(a != b),
+            op => {
+                panic!("unsupported bool operator {op:?}")
+            }
+        },
+        (a, b) => {
+            panic!("can't apply {op:?} to {a:?}, {b:?}")
+        }
+    })
+}
+
+fn math(
+    fun: MathFunction,
+    arg: &Value,
+    arg1: Option<&Value>,
+    arg2: Option<&Value>,
+) -> Value {
+    use MathFunction::*;
+    let unary = |f: fn(f32) -> f32| {
+        arg.map(&|value| Value::F32(f(value.f32())))
+    };
+    let binary = |f: fn(f32, f32) -> f32| {
+        arg.zip(arg1.unwrap(), &|a, b| {
+            Value::F32(f(a.f32(), b.f32()))
+        })
+    };
+    let ternary = |f: fn(f32, f32, f32) -> f32| {
+        arg.zip3(arg1.unwrap(), arg2.unwrap(), &f)
+    };
+    let dot = |a: &Value, b: &Value| {
+        a.components()
+            .iter()
+            .zip(b.components())
+            .map(|(a, b)| a.f32() * b.f32())
+            .sum::<f32>()
+    };
+    match fun {
+        Abs => unary(f32::abs),
+        Floor => unary(f32::floor),
+        Ceil => unary(f32::ceil),
+        Fract => unary(|x| x - x.floor()),
+        Trunc => unary(f32::trunc),
+        // WGSL rounds halves to even
+        Round => unary(|x| {
+            if (x.round() - x).abs() == 0.5 {
+                2. * (x / 2.).round()
+            } else {
+                x.round()
+            }
+        }),
+        Sqrt => unary(f32::sqrt),
+        InverseSqrt => unary(|x| 1. / x.sqrt()),
+        Sin => unary(f32::sin),
+        Cos => unary(f32::cos),
+        Tan => unary(f32::tan),
+        Exp => unary(f32::exp),
+        Exp2 => unary(f32::exp2),
+        Log => unary(f32::ln),
+        Log2 => unary(f32::log2),
+        Sign => unary(|x| {
+            if x > 0. {
+                1.
+            } else if x < 0. {
+                -1.
+            } else {
+                0.
+            }
+        }),
+        Saturate => unary(|x| x.clamp(0., 1.)),
+        Min => binary(f32::min),
+        Max => binary(f32::max),
+        Pow => binary(f32::powf),
+        Atan2 => binary(f32::atan2),
+        Step => binary(
+            |edge, x| if x >= edge { 1. } else { 0. },
+        ),
+        Clamp => {
+            ternary(|x, low, high| x.max(low).min(high))
+        }
+        Mix => ternary(|x, y, a| x * (1. - a) + y * a),
+        SmoothStep => ternary(|edge0, edge1, x| {
+            let t = ((x - edge0) / (edge1 - edge0))
+                .clamp(0., 1.);
+            t * t * (3. - 2. * t)
+        }),
+        Fma => ternary(|a, b, c| a * b + c),
+        Dot => Value::F32(dot(arg, arg1.unwrap())),
+        Length => match arg {
+            Value::F32(x) => Value::F32(x.abs()),
+            vector => {
+                Value::F32(dot(vector, vector).sqrt())
+            }
+        },
+        Distance => {
+            let d = binary(|a, b| a - b);
+            Value::F32(dot(&d, &d).sqrt())
+        }
+        Normalize => {
+            let length = dot(arg, arg).sqrt();
+            arg.map(&|value| {
+                Value::F32(value.f32() * (1. / length))
+            })
+        }
+        Cross => {
+            let a = arg.components();
+            let b = arg1.unwrap().components();
+            let [ax, ay, az] =
+                [0, 1, 2].map(|i| a[i].f32());
+            let [bx, by, bz] =
+                [0, 1, 2].map(|i| b[i].f32());
+            Value::from(Vec3::new(
+                ay * bz - az * by,
+                az * bx - ax * bz,
+                ax * by - ay * bx,
+            ))
+        }
+        fun => panic!("unsupported math function {fun:?}"),
+    }
+}
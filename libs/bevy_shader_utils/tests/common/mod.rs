@@ -0,0 +1,292 @@
+// every test binary only uses part of the harness
+#![allow(dead_code)]
+
+pub mod interpreter;
+
+use interpreter::Interpreter;
+
+use bevy::{
+    asset::HandleId,
+    audio::AudioPlugin,
+    log::LogPlugin,
+    prelude::*,
+    render::{
+        render_resource::{
+            ProcessedShader, Shader, ShaderImport,
+            ShaderProcessor,
+        },
+        settings::WgpuSettings,
+    },
+    utils::HashMap,
+    winit::WinitPlugin,
+};
+use bevy_shader_utils::*;
+
+/// Modules that expect `noise_common` imported before them
+pub const NEEDS_NOISE_COMMON: &[&str] = &[
+    "bevy_shader_utils::perlin_noise_2d",
+    "bevy_shader_utils::perlin_noise_3d",
+    "bevy_shader_utils::simplex_noise_2d",
+    "bevy_shader_utils::simplex_noise_3d",
+    "bevy_shader_utils::simplex_noise_4d",
+    "bevy_shader_utils::curl_noise_2d",
+    "bevy_shader_utils::curl_noise_3d",
+    "bevy_shader_utils::fbm_perlin_2d",
+    "bevy_shader_utils::fbm_perlin_3d",
+    "bevy_shader_utils::fbm_simplex_2d",
+    "bevy_shader_utils::fbm_simplex_3d",
+];
+
+/// Modules that call a base noise they expect imported before
+/// them, next to that base noise
+pub const BASE_NOISES: &[(&str, &str)] = &[
+    (
+        "bevy_shader_utils::curl_noise_2d",
+        "bevy_shader_utils::simplex_noise_2d",
+    ),
+    (
+        "bevy_shader_utils::curl_noise_3d",
+        "bevy_shader_utils::simplex_noise_3d",
+    ),
+    (
+        "bevy_shader_utils::fbm",
+        "bevy_shader_utils::value_noise_2d",
+    ),
+    (
+        "bevy_shader_utils::fbm_perlin_2d",
+        "bevy_shader_utils::perlin_noise_2d",
+    ),
+    (
+        "bevy_shader_utils::fbm_perlin_3d",
+        "bevy_shader_utils::perlin_noise_3d",
+    ),
+    (
+        "bevy_shader_utils::fbm_simplex_2d",
+        "bevy_shader_utils::simplex_noise_2d",
+    ),
+    (
+        "bevy_shader_utils::fbm_simplex_3d",
+        "bevy_shader_utils::simplex_noise_3d",
+    ),
+    (
+        "bevy_shader_utils::fbm_value_2d",
+        "bevy_shader_utils::value_noise_2d",
+    ),
+    (
+        "bevy_shader_utils::fbm_value_3d",
+        "bevy_shader_utils::value_noise_3d",
+    ),
+];
+
+/// `imports` the way the README has users write them:
+/// `noise_common` first when needed, every base noise before the
+/// modules calling it, and each module once
+pub fn with_dependencies<'a>(
+    imports: &[&'a str],
+) -> Vec<&'a str> {
+    let mut lines = Vec::new();
+    if imports
+        .iter()
+        .any(|path| NEEDS_NOISE_COMMON.contains(path))
+    {
+        lines.push("bevy_shader_utils::noise_common");
+    }
+    for path in imports {
+        let base = BASE_NOISES
+            .iter()
+            .find(|(module, _)| module == path)
+            .map(|(_, base)| *base);
+        for import in base.into_iter().chain([*path]) {
+            if !lines.contains(&import) {
+                lines.push(import);
+            }
+        }
+    }
+    lines
+}
+
+/// Modules that call a `sceneSdf` the importing shader defines
+pub const NEEDS_SCENE: &[&str] =
+    &["bevy_shader_utils::raymarch"];
+
+/// The scene test shaders define for them, a unit sphere
+pub const SCENE: &str =
+    "fn sceneSdf(p: vec3<f32>) -> f32 { return length(p) - 1.; }\n";
+
+/// Resolves `#import`s with Bevy's own shader processor, without
+/// needing an app or a GPU
+pub struct ShaderModules {
+    shaders: HashMap<Handle<Shader>, Shader>,
+    imports: HashMap<ShaderImport, Handle<Shader>>,
+}
+
+impl ShaderModules {
+    /// Our modules next to Bevy's own, like
+    /// `bevy_pbr::mesh_functions`, taken from an app without a
+    /// renderer
+    pub fn with_bevy() -> Self {
+        let mut app = App::new();
+        app.insert_resource(WgpuSettings {
+            backends: None,
+            ..default()
+        })
+        .add_plugins(
+            DefaultPlugins
+                .set(WindowPlugin {
+                    add_primary_window: false,
+                    ..default()
+                })
+                .disable::<AudioPlugin>()
+                .disable::<LogPlugin>()
+                .disable::<WinitPlugin>(),
+        );
+
+        let mut shader_modules = ShaderModules::default();
+        let shaders =
+            app.world.resource::<Assets<Shader>>();
+        for (_, shader) in shaders.iter() {
+            if shader.import_path().is_some() {
+                shader_modules.insert(shader.clone());
+            }
+        }
+        shader_modules
+    }
+
+    pub fn insert(&mut self, shader: Shader) {
+        let import = shader
+            .import_path()
+            .expect("modules need an import path")
+            .clone();
+        let handle =
+            Handle::weak(HandleId::random::<Shader>());
+        self.shaders.insert(handle.clone_weak(), shader);
+        self.imports.insert(import, handle);
+    }
+
+    /// The source with every `#import` pasted in
+    pub fn process(
+        &self,
+        source: &str,
+        shader_defs: &[String],
+    ) -> Result<String, String> {
+        let shader = Shader::from_wgsl(source.to_string());
+        match ShaderProcessor::default().process(
+            &shader,
+            shader_defs,
+            &self.shaders,
+            &self.imports,
+        ) {
+            Ok(ProcessedShader::Wgsl(source)) => {
+                Ok(source.into_owned())
+            }
+            Ok(_) => Err("not wgsl".to_string()),
+            Err(err) => Err(err.to_string()),
+        }
+    }
+}
+
+impl Default for ShaderModules {
+    /// The built-in modules
+    fn default() -> Self {
+        let mut shader_modules = ShaderModules {
+            shaders: HashMap::default(),
+            imports: HashMap::default(),
+        };
+        for (path, source) in import_paths() {
+            shader_modules.insert(
+                Shader::from_wgsl(source)
+                    .with_import_path(path),
+            );
+        }
+        shader_modules
+    }
+}
+
+/// The built-in modules by import path
+pub fn import_paths() -> Vec<(String, String)> {
+    built_in_modules()
+        .map(|(name, source)| {
+            (
+                format!("{NAMESPACE}::{name}"),
+                source.into_owned(),
+            )
+        })
+        .collect()
+}
+
+/// Parses and validates processed WGSL with naga
+pub fn validate(source: &str) -> Result<(), String> {
+    let module = naga::front::wgsl::parse_str(source)
+        .map_err(|err| err.emit_to_string(source))?;
+    naga::valid::Validator::new(
+        naga::valid::ValidationFlags::all(),
+        naga::valid::Capabilities::all(),
+    )
+    .validate(&module)
+    .map_err(|err| format!("{err:?}"))?;
+    Ok(())
+}
+
+/// The interpreter over a shader made of the `imports` alone
+pub fn shader(imports: &[&str]) -> Interpreter {
+    let modules = ShaderModules::default();
+    Interpreter::new(
+        parse_imports(&modules, imports).unwrap(),
+    )
+}
+
+/// The interpreter over a noise module and what it needs
+pub fn noise_shader(path: &str) -> Interpreter {
+    shader(&with_dependencies(&[path]))
+}
+
+/// Points off the lattice, negative ones included, and off the
+/// diagonals that simplex cells meet along
+pub fn grid_2d() -> impl Iterator<Item = Vec2> {
+    (-6..6).flat_map(|y| {
+        (-6..6).map(move |x| {
+            Vec2::new(x as f32 * 0.73, y as f32 * 0.91)
+                + Vec2::new(0.13, 0.37)
+        })
+    })
+}
+
+pub fn grid_3d() -> impl Iterator<Item = Vec3> {
+    (-3..3).flat_map(|z| {
+        grid_2d()
+            .step_by(2)
+            .map(move |p| p.extend(z as f32 * 1.37 + 0.29))
+    })
+}
+
+/// Processes and parses a shader made of the `imports` alone
+pub fn parse_imports(
+    modules: &ShaderModules,
+    imports: &[&str],
+) -> Result<naga::Module, String> {
+    let source =
+        modules.process(&import_lines(imports), &[])?;
+    naga::front::wgsl::parse_str(&source)
+        .map_err(|err| err.emit_to_string(&source))
+}
+
+/// Processes and validates a shader made of the `imports` alone
+pub fn validate_imports(
+    modules: &ShaderModules,
+    imports: &[&str],
+) -> Result<(), String> {
+    validate(&modules.process(&import_lines(imports), &[])?)
+}
+
+fn import_lines(imports: &[&str]) -> String {
+    let mut source = String::new();
+    // naga wants functions defined before they're called
+    if imports.iter().any(|path| NEEDS_SCENE.contains(path))
+    {
+        source.push_str(SCENE);
+    }
+    for path in imports {
+        source.push_str(&format!("#import {path}\n"));
+    }
+    source
+}
@@ -0,0 +1,109 @@
+mod common;
+
+use bevy::math::{Vec2, Vec3};
+use bevy_shader_utils::*;
+use common::*;
+
+/// Small enough that smooth noise barely moves from one sample to
+/// the next, so a jump at a cell edge stands out
+const STEP: f32 = 1e-3;
+/// The most the noise may change over one step
+const MAX_CHANGE: f32 = 0.02;
+
+fn assert_continuous(
+    name: &str,
+    values: impl Iterator<Item = f32>,
+) {
+    let mut previous: Option<f32> = None;
+    for (index, value) in values.enumerate() {
+        if let Some(previous) = previous {
+            assert!(
+                (value - previous).abs() <= MAX_CHANGE,
+                "{name} jumps from {previous} to {value} \
+                 at step {index}",
+            );
+        }
+        previous = Some(value);
+    }
+}
+
+/// A few units along a line at an odd angle, crossing cell edges
+/// of every orientation
+fn walk_2d() -> impl Iterator<Item = Vec2> {
+    (0..4000).map(|i| {
+        Vec2::new(-3.1, -2.3)
+            + Vec2::new(0.83, 0.57) * i as f32 * STEP
+    })
+}
+
+/// Like `walk_2d`, climbing through integer z as well
+fn walk_3d() -> impl Iterator<Item = Vec3> {
+    (0..4000).map(|i| {
+        Vec3::new(-3.1, -2.3, -1.7)
+            + Vec3::new(0.71, 0.49, 0.5) * i as f32 * STEP
+    })
+}
+
+#[test]
+fn simplex_noise_2d_is_continuous() {
+    assert_continuous(
+        "simplex_noise_2d",
+        walk_2d().map(simplex_noise_2d),
+    );
+    let shader =
+        noise_shader("bevy_shader_utils::simplex_noise_2d");
+    assert_continuous(
+        "simplexNoise2",
+        walk_2d().map(|p| {
+            shader.call("simplexNoise2", &[p.into()]).f32()
+        }),
+    );
+}
+
+#[test]
+fn perlin_noise_3d_is_continuous() {
+    assert_continuous(
+        "perlin_noise_3d",
+        walk_3d().map(perlin_noise_3d),
+    );
+    let shader =
+        noise_shader("bevy_shader_utils::perlin_noise_3d");
+    assert_continuous(
+        "perlinNoise3",
+        walk_3d().map(|p| {
+            shader.call("perlinNoise3", &[p.into()]).f32()
+        }),
+    );
+}
+
+#[test]
+fn perlin_noise_2d_is_continuous() {
+    assert_continuous(
+        "perlin_noise_2d",
+        walk_2d().map(perlin_noise_2d),
+    );
+    let shader =
+        noise_shader("bevy_shader_utils::perlin_noise_2d");
+    assert_continuous(
+        "perlinNoise2",
+        walk_2d().map(|p| {
+            shader.call("perlinNoise2", &[p.into()]).f32()
+        }),
+    );
+}
+
+#[test]
+fn simplex_noise_3d_is_continuous() {
+    assert_continuous(
+        "simplex_noise_3d",
+        walk_3d().map(simplex_noise_3d),
+    );
+    let shader =
+        noise_shader("bevy_shader_utils::simplex_noise_3d");
+    assert_continuous(
+        "simplexNoise3",
+        walk_3d().map(|p| {
+            shader.call("simplexNoise3", &[p.into()]).f32()
+        }),
+    );
+}
@@ -0,0 +1,63 @@
+mod common;
+
+use common::*;
+
+/// Clashes are between two modules, so every pair covers them
+/// without going through every combination. Pairs sharing a base
+/// noise, like `fbm_simplex_2d` and `curl_noise_2d`, import it
+/// once.
+#[test]
+fn every_pair_of_imports_composes() {
+    let modules = ShaderModules::default();
+    let paths = import_paths();
+    let mut pairs = 0;
+    let mut failures = Vec::new();
+    for (index, (a, _)) in paths.iter().enumerate() {
+        for (b, _) in &paths[index + 1..] {
+            let imports = with_dependencies(&[
+                a.as_str(),
+                b.as_str(),
+            ]);
+            pairs += 1;
+            if let Err(err) =
+                validate_imports(&modules, &imports)
+            {
+                failures
+                    .push(format!("{imports:?}\n{err}"));
+            }
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "{} of {pairs} pairs failed, e.g. {}",
+        failures.len(),
+        failures[0],
+    );
+}
+
+#[test]
+fn every_module_composes() {
+    let modules = ShaderModules::default();
+    let paths = import_paths();
+    let paths: Vec<&str> = paths
+        .iter()
+        .map(|(path, _)| path.as_str())
+        .collect();
+    let imports = with_dependencies(&paths);
+    if let Err(err) = validate_imports(&modules, &imports) {
+        panic!("{imports:?}\n{err}");
+    }
+}
+
+#[test]
+fn combinations_are_order_independent() {
+    let modules = ShaderModules::default();
+    let paths = import_paths();
+    let mut paths: Vec<&str> = paths
+        .iter()
+        .map(|(path, _)| path.as_str())
+        .collect();
+    paths.reverse();
+    validate_imports(&modules, &with_dependencies(&paths))
+        .unwrap();
+}
@@ -0,0 +1,303 @@
+mod common;
+
+use bevy::math::{Vec2, Vec3, Vec4};
+use bevy_shader_utils::{
+    fbm_module, FBM_NOISES, NAMESPACE,
+};
+use common::{interpreter::*, *};
+
+/// One-sided differences of `f` along each axis of `p`, a step
+/// of `h` away. Comparing against both sides leaves out the side
+/// that crosses one of 3D simplex noise's faint seams, where the
+/// 0.6 kernel of the original overlaps the next cell.
+fn finite_differences(
+    f: impl Fn(&[f32]) -> f32,
+    p: &[f32],
+    h: f32,
+) -> Vec<f32> {
+    (0..p.len())
+        .map(|axis| {
+            let mut q = p.to_vec();
+            q[axis] += h;
+            (f(&q) - f(p)) / h
+        })
+        .collect()
+}
+
+const H: f32 = 1e-4;
+
+/// Whether `name` returns its value in `x` followed by the
+/// gradient of `value_name`
+fn assert_derivatives(
+    path: &str,
+    value_name: &str,
+    name: &str,
+    points: impl Iterator<Item = Vec<f32>>,
+) {
+    let shader = noise_shader(path);
+    let call =
+        |name: &str, p: &[f32]| match p.len() {
+            2 => shader
+                .call(name, &[Vec2::from_slice(p).into()]),
+            _ => shader
+                .call(name, &[Vec3::from_slice(p).into()]),
+        };
+    let mut largest = 0f32;
+    for p in points {
+        let result = call(name, &p).f32s();
+        let value = call(value_name, &p).f32();
+        assert!(
+            (result[0] - value).abs() < 1e-5,
+            "{name}{p:?} gives {result:?}, {value_name} {value}",
+        );
+        let value = |q: &[f32]| call(value_name, q).f32();
+        let ahead = finite_differences(value, &p, H);
+        let behind = finite_differences(value, &p, -H);
+        for (axis, actual) in result[1..].iter().enumerate()
+        {
+            assert!(
+                (actual - ahead[axis]).abs() < 0.02
+                    || (actual - behind[axis]).abs() < 0.02,
+                "{name}{p:?} gives {result:?}, \
+                 the differences {ahead:?} and {behind:?}",
+            );
+            largest = largest.max(actual.abs());
+        }
+    }
+    assert!(
+        largest > 0.5,
+        "{name} gradients up to {largest}"
+    );
+}
+
+/// The divergence of `field` at `p`, the smaller of the two
+/// sides'
+fn divergence(
+    field: impl Fn(&[f32]) -> Vec<f32>,
+    p: &[f32],
+) -> f32 {
+    [H, -H]
+        .map(|h| {
+            (0..p.len())
+                .map(|axis| {
+                    finite_differences(
+                        |q| field(q)[axis],
+                        p,
+                        h,
+                    )[axis]
+                })
+                .sum::<f32>()
+        })
+        .into_iter()
+        .min_by(|a, b| a.abs().total_cmp(&b.abs()))
+        .unwrap()
+}
+
+#[test]
+fn simplex_noise_2d_derivatives() {
+    assert_derivatives(
+        "bevy_shader_utils::simplex_noise_2d",
+        "simplexNoise2",
+        "simplexNoiseDeriv2",
+        grid_2d().map(|p| p.to_array().to_vec()),
+    );
+}
+
+#[test]
+fn simplex_noise_3d_derivatives() {
+    assert_derivatives(
+        "bevy_shader_utils::simplex_noise_3d",
+        "simplexNoise3",
+        "simplexNoiseDeriv3",
+        grid_3d().map(|p| p.to_array().to_vec()),
+    );
+}
+
+#[test]
+fn value_noise_2d_derivatives() {
+    assert_derivatives(
+        "bevy_shader_utils::value_noise_2d",
+        "valueNoise2",
+        "valueNoiseDeriv2",
+        grid_2d().map(|p| p.to_array().to_vec()),
+    );
+}
+
+#[test]
+fn value_noise_3d_derivatives() {
+    assert_derivatives(
+        "bevy_shader_utils::value_noise_3d",
+        "valueNoise3",
+        "valueNoiseDeriv3",
+        grid_3d().map(|p| p.to_array().to_vec()),
+    );
+}
+
+#[test]
+fn curl_noise_2d_is_divergence_free() {
+    let shader =
+        noise_shader("bevy_shader_utils::curl_noise_2d");
+    let curl = |q: &[f32]| {
+        shader
+            .call(
+                "curlNoise2",
+                &[Vec2::from_slice(q).into()],
+            )
+            .f32s()
+    };
+    for p in grid_2d() {
+        let divergence = divergence(curl, &p.to_array());
+        assert!(
+            divergence.abs() < 0.05,
+            "{p}: {divergence}"
+        );
+    }
+}
+
+#[test]
+fn curl_noise_3d_is_divergence_free() {
+    let shader =
+        noise_shader("bevy_shader_utils::curl_noise_3d");
+    let curl = |q: &[f32]| {
+        shader
+            .call(
+                "curlNoise3",
+                &[Vec3::from_slice(q).into()],
+            )
+            .f32s()
+    };
+    let mut largest = 0f32;
+    let mut samples = 0;
+    let mut diverging = Vec::new();
+    for p in grid_3d().step_by(3) {
+        largest = largest.max(
+            Vec3::from_slice(&curl(&p.to_array())).length(),
+        );
+        let divergence = divergence(curl, &p.to_array());
+        samples += 1;
+        if divergence.abs() >= 0.05 {
+            diverging.push((p, divergence));
+        }
+    }
+    // the three potential fields each have seams, and next to
+    // one a step either way can cross one
+    assert!(
+        diverging.len() * 10 <= samples,
+        "{} of {samples} diverge: {diverging:?}",
+        diverging.len(),
+    );
+    assert!(largest > 0.5, "curl up to {largest}");
+}
+
+#[test]
+fn simplex_noise_4d_stays_in_range() {
+    let shader =
+        noise_shader("bevy_shader_utils::simplex_noise_4d");
+    let mut min = f32::MAX;
+    let mut max = f32::MIN;
+    for p in grid_3d() {
+        for w in [-2.7, 0.4, 3.1] {
+            let p = p.extend(w);
+            let value = shader
+                .call("simplexNoise4", &[p.into()])
+                .f32();
+            // nearby points give nearby values
+            let near = shader
+                .call(
+                    "simplexNoise4",
+                    &[(p + Vec4::splat(1e-3)).into()],
+                )
+                .f32();
+            assert!((value - near).abs() < 0.05, "{p}");
+            min = min.min(value);
+            max = max.max(value);
+        }
+    }
+    assert!(-1. <= min && max <= 1., "{min}..{max}");
+    assert!(max - min > 0.5, "{min}..{max}");
+}
+
+#[test]
+fn worley_noise_orders_its_distances() {
+    let shader_2d =
+        noise_shader("bevy_shader_utils::worley_noise_2d");
+    let shader_3d =
+        noise_shader("bevy_shader_utils::worley_noise_3d");
+    let samples = grid_2d()
+        .map(|p| {
+            shader_2d.call("worleyNoise2", &[p.into()])
+        })
+        .chain(grid_3d().map(|p| {
+            shader_3d.call("worleyNoise3", &[p.into()])
+        }));
+    let mut largest = 0f32;
+    for sample in samples {
+        let [f1, f2] = sample.f32s()[..] else {
+            panic!("expected a vec2");
+        };
+        assert!(
+            0. <= f1 && f1 <= f2 && f2 < 2.,
+            "{f1}, {f2}"
+        );
+        largest = largest.max(f2 - f1);
+    }
+    assert!(largest > 0.3, "F2 - F1 up to {largest}");
+}
+
+#[test]
+fn fbm_modules_shape_their_base_noise() {
+    for (noise, dimensions) in FBM_NOISES {
+        let (name, source) = fbm_module(noise, *dimensions);
+        assert!(!source.contains("{{"), "{name}");
+        let shader =
+            noise_shader(&format!("{NAMESPACE}::{name}"));
+        let points: Vec<Value> = match dimensions {
+            2 => grid_2d().map(Value::from).collect(),
+            _ => grid_3d().map(Value::from).collect(),
+        };
+        let call = |function: &str, p: &Value, octaves| {
+            shader
+                .call(
+                    &format!(
+                        "{noise}{function}{dimensions}"
+                    ),
+                    &[
+                        p.clone(),
+                        Value::I32(octaves),
+                        2.0.into(),
+                        0.5.into(),
+                    ],
+                )
+                .f32()
+        };
+        for p in &points {
+            // a single octave is the base noise, shaped
+            let n = shader
+                .call(
+                    &format!("{noise}Noise{dimensions}"),
+                    std::slice::from_ref(p),
+                )
+                .f32();
+            let shapes = [
+                ("Fbm", n),
+                ("Turbulence", n.abs()),
+                ("Ridged", (1. - n.abs()) * (1. - n.abs())),
+                ("Billow", 2. * n.abs() - 1.),
+            ];
+            for (function, expected) in shapes {
+                let value = call(function, p, 1);
+                assert!(
+                    (value - expected).abs() < 1e-6,
+                    "{noise}{function}{dimensions} gives \
+                     {value}, expected {expected}",
+                );
+                let value = call(function, p, 5);
+                assert!(
+                    (-1. ..=1.).contains(&value),
+                    "{noise}{function}{dimensions} gives \
+                     {value}",
+                );
+            }
+        }
+    }
+}
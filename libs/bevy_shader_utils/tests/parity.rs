@@ -0,0 +1,115 @@
+mod common;
+
+use bevy::math::Vec3;
+use bevy_shader_utils::*;
+use common::{interpreter::*, *};
+
+/// How far the Rust ports may stray from the WGSL run on the CPU
+const TOLERANCE: f32 = 1e-5;
+
+fn assert_parity(
+    name: &str,
+    samples: impl Iterator<Item = (Vec<Value>, f32)>,
+    shader: &Interpreter,
+) {
+    let mut min = f32::MAX;
+    let mut max = f32::MIN;
+    for (arguments, expected) in samples {
+        let actual = shader.call(name, &arguments).f32();
+        assert!(
+            (actual - expected).abs() <= TOLERANCE,
+            "{name}{arguments:?}: the shader gives {actual}, \
+             the port {expected}",
+        );
+        min = min.min(actual);
+        max = max.max(actual);
+    }
+    // make sure the grid samples something more than a constant
+    assert!(max - min > 0.5, "{name} spans {min}..{max}");
+}
+
+#[test]
+fn perlin_noise_2d_matches_the_shader() {
+    assert_parity(
+        "perlinNoise2",
+        grid_2d()
+            .map(|p| (vec![p.into()], perlin_noise_2d(p))),
+        &noise_shader("bevy_shader_utils::perlin_noise_2d"),
+    );
+}
+
+#[test]
+fn perlin_noise_3d_matches_the_shader() {
+    assert_parity(
+        "perlinNoise3",
+        grid_3d()
+            .map(|p| (vec![p.into()], perlin_noise_3d(p))),
+        &noise_shader("bevy_shader_utils::perlin_noise_3d"),
+    );
+}
+
+#[test]
+fn simplex_noise_2d_matches_the_shader() {
+    assert_parity(
+        "simplexNoise2",
+        grid_2d()
+            .map(|p| (vec![p.into()], simplex_noise_2d(p))),
+        &noise_shader(
+            "bevy_shader_utils::simplex_noise_2d",
+        ),
+    );
+}
+
+#[test]
+fn simplex_noise_3d_matches_the_shader() {
+    assert_parity(
+        "simplexNoise3",
+        grid_3d()
+            .map(|p| (vec![p.into()], simplex_noise_3d(p))),
+        &noise_shader(
+            "bevy_shader_utils::simplex_noise_3d",
+        ),
+    );
+}
+
+#[test]
+fn voronoise_matches_the_shader() {
+    let shader = shader(&["bevy_shader_utils::voronoise"]);
+    for (u, v) in [(0., 0.), (1., 0.), (0., 1.), (0.5, 0.7)]
+    {
+        assert_parity(
+            "voronoise",
+            grid_2d().step_by(4).map(|p| {
+                (
+                    vec![p.into(), u.into(), v.into()],
+                    voronoise(p, u, v),
+                )
+            }),
+            &shader,
+        );
+    }
+}
+
+#[test]
+fn mock_fresnel_matches_the_shader() {
+    let shader =
+        shader(&["bevy_shader_utils::mock_fresnel"]);
+    let camera = Vec3::new(0., 2., 5.);
+    assert_parity(
+        "mock_fresnel",
+        grid_3d().step_by(7).map(|p| {
+            let normal = p.normalize();
+            (
+                vec![
+                    camera.into(),
+                    p.into(),
+                    normal.into(),
+                    3.0.into(),
+                    2.0.into(),
+                ],
+                mock_fresnel(camera, p, normal, 3., 2.),
+            )
+        }),
+        &shader,
+    );
+}
@@ -0,0 +1,231 @@
+mod common;
+
+use bevy::math::{Vec2, Vec3};
+use common::{interpreter::*, *};
+
+/// Whether `noise` repeats every `period`, is continuous and
+/// spans more than a constant
+fn assert_tiles(
+    name: &str,
+    noise: impl Fn(&[f32]) -> f32,
+    period: &[f32],
+    points: impl Iterator<Item = Vec<f32>>,
+) {
+    let mut min = f32::MAX;
+    let mut max = f32::MIN;
+    for p in points {
+        let value = noise(&p);
+        for (axis, repeats) in
+            [(0, 1.), (1, -1.), (2, 2.)].into_iter()
+        {
+            if axis >= p.len() {
+                continue;
+            }
+            let mut q = p.clone();
+            q[axis] += period[axis] * repeats;
+            let repeated = noise(&q);
+            assert!(
+                (value - repeated).abs() < 1e-4,
+                "{name}{p:?} is {value}, {repeated} \
+                 {repeats} periods along axis {axis}",
+            );
+        }
+        min = min.min(value);
+        max = max.max(value);
+    }
+    assert!(max - min > 0.3, "{name} spans {min}..{max}");
+
+    // no seams, walking across a few periods in small steps
+    let direction = [1., 0.7, 0.3];
+    let mut p = vec![-0.3; period.len()];
+    let mut value = noise(&p);
+    for _ in 0..2000 {
+        for (x, d) in p.iter_mut().zip(direction) {
+            *x += d * 5e-3;
+        }
+        let next = noise(&p);
+        assert!(
+            (value - next).abs() < 0.05,
+            "{name}{p:?} jumps from {value} to {next}",
+        );
+        value = next;
+    }
+}
+
+fn vec2_noise<'a>(
+    shader: &'a Interpreter,
+    name: &'a str,
+    period: Vec2,
+) -> impl Fn(&[f32]) -> f32 + 'a {
+    move |p| {
+        shader
+            .call(
+                name,
+                &[
+                    Vec2::from_slice(p).into(),
+                    period.into(),
+                ],
+            )
+            .f32()
+    }
+}
+
+fn vec3_noise<'a>(
+    shader: &'a Interpreter,
+    name: &'a str,
+    period: Vec3,
+) -> impl Fn(&[f32]) -> f32 + 'a {
+    move |p| {
+        shader
+            .call(
+                name,
+                &[
+                    Vec3::from_slice(p).into(),
+                    period.into(),
+                ],
+            )
+            .f32()
+    }
+}
+
+#[test]
+fn perlin_noise_2d_tiles() {
+    let shader =
+        noise_shader("bevy_shader_utils::perlin_noise_2d");
+    let period = Vec2::new(4., 3.);
+    assert_tiles(
+        "perlinNoisePeriodic2",
+        vec2_noise(&shader, "perlinNoisePeriodic2", period),
+        &period.to_array(),
+        grid_2d().map(|p| p.to_array().to_vec()),
+    );
+}
+
+#[test]
+fn perlin_noise_3d_tiles() {
+    let shader =
+        noise_shader("bevy_shader_utils::perlin_noise_3d");
+    let period = Vec3::new(4., 3., 5.);
+    assert_tiles(
+        "perlinNoisePeriodic3",
+        vec3_noise(&shader, "perlinNoisePeriodic3", period),
+        &period.to_array(),
+        grid_3d().map(|p| p.to_array().to_vec()),
+    );
+}
+
+#[test]
+fn simplex_noise_2d_tiles() {
+    let shader =
+        noise_shader("bevy_shader_utils::simplex_noise_2d");
+    let period = Vec2::new(3., 4.);
+    assert_tiles(
+        "simplexNoisePeriodic2",
+        vec2_noise(
+            &shader,
+            "simplexNoisePeriodic2",
+            period,
+        ),
+        &period.to_array(),
+        grid_2d().map(|p| p.to_array().to_vec()),
+    );
+}
+
+#[test]
+fn simplex_noise_3d_tiles() {
+    let shader =
+        noise_shader("bevy_shader_utils::simplex_noise_3d");
+    let period = Vec3::new(4., 3., 5.);
+    assert_tiles(
+        "simplexNoisePeriodic3",
+        vec3_noise(
+            &shader,
+            "simplexNoisePeriodic3",
+            period,
+        ),
+        &period.to_array(),
+        grid_3d().map(|p| p.to_array().to_vec()),
+    );
+}
+
+#[test]
+fn voronoise_tiles() {
+    let shader = shader(&["bevy_shader_utils::voronoise"]);
+    let period = Vec2::new(4., 3.);
+    assert_tiles(
+        "voronoisePeriodic",
+        |p| {
+            shader
+                .call(
+                    "voronoisePeriodic",
+                    &[
+                        Vec2::from_slice(p).into(),
+                        0.5.into(),
+                        0.7.into(),
+                        period.into(),
+                    ],
+                )
+                .f32()
+        },
+        &period.to_array(),
+        grid_2d().step_by(2).map(|p| p.to_array().to_vec()),
+    );
+}
+
+/// Within one period, away from its edges, the periodic noise
+/// is the regular one
+#[test]
+fn long_periods_match_the_regular_noise() {
+    let perlin_2d =
+        noise_shader("bevy_shader_utils::perlin_noise_2d");
+    let perlin_3d =
+        noise_shader("bevy_shader_utils::perlin_noise_3d");
+    let voronoise =
+        shader(&["bevy_shader_utils::voronoise"]);
+    let period = 100.;
+    for p in grid_2d().map(|p| p + 10.) {
+        assert_eq!(
+            perlin_2d
+                .call("perlinNoise2", &[p.into()])
+                .f32(),
+            perlin_2d
+                .call(
+                    "perlinNoisePeriodic2",
+                    &[p.into(), Vec2::splat(period).into()],
+                )
+                .f32(),
+        );
+        assert_eq!(
+            voronoise
+                .call(
+                    "voronoise",
+                    &[p.into(), 0.5.into(), 0.7.into()]
+                )
+                .f32(),
+            voronoise
+                .call(
+                    "voronoisePeriodic",
+                    &[
+                        p.into(),
+                        0.5.into(),
+                        0.7.into(),
+                        Vec2::splat(period).into(),
+                    ],
+                )
+                .f32(),
+        );
+    }
+    for p in grid_3d().map(|p| p + 10.) {
+        assert_eq!(
+            perlin_3d
+                .call("perlinNoise3", &[p.into()])
+                .f32(),
+            perlin_3d
+                .call(
+                    "perlinNoisePeriodic3",
+                    &[p.into(), Vec3::splat(period).into()],
+                )
+                .f32(),
+        );
+    }
+}
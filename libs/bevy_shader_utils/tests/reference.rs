@@ -0,0 +1,468 @@
+//! Checks the noise ports against the GLSL they come from rather
+//! than against the WGSL: Stefan Gustavson's classic Perlin noise
+//! (`classicnoise2D.glsl`, `classicnoise3D.glsl`) and Ian McEwan's
+//! simplex noise (`noise2D.glsl`, `noise3D.glsl`) from the 2011
+//! webgl-noise release, transcribed line by line below.
+//!
+//! The transcription runs in f32 like the GLSL does on a GPU. The
+//! 3D gradients fold over an octahedron's edges where a coordinate
+//! is exactly zero, and which way they go there is down to float
+//! rounding, which doubles would get differently. For the same
+//! reason `cnoise(vec3)` divides by 7 where the GLSL multiplies by
+//! `1.0 / 7.0`, as the WGSL port does: the two round apart right at
+//! those edges.
+//!
+//! GLSL's `mod` floors where WGSL's `%` truncates, so the two only
+//! hash the same cells at positive coordinates, which is where the
+//! reference values are taken.
+
+// the constants are kept exactly as the GLSL spells them
+#![allow(clippy::excessive_precision)]
+
+mod common;
+
+use bevy::math::{Vec2, Vec3, Vec4, Vec4Swizzles};
+use bevy_shader_utils::*;
+use common::*;
+
+/// How far the ports may stray from the originals, which do the
+/// same math in a different order
+const TOLERANCE: f32 = 1e-5;
+/// Moves the shared sample grids to positive coordinates
+const SHIFT: f32 = 7.;
+
+// webgl-noise's helpers
+
+fn mod289(x: Vec4) -> Vec4 {
+    x - (x * (1. / 289.)).floor() * 289.
+}
+
+fn permute(x: Vec4) -> Vec4 {
+    mod289(((x * 34.) + 1.) * x)
+}
+
+fn taylor_inv_sqrt(r: Vec4) -> Vec4 {
+    1.79284291400159 - 0.85373472095314 * r
+}
+
+fn fract(x: Vec4) -> Vec4 {
+    x - x.floor()
+}
+
+fn step(edge: Vec4, x: Vec4) -> Vec4 {
+    Vec4::select(x.cmplt(edge), Vec4::ZERO, Vec4::ONE)
+}
+
+fn fade(t: f32) -> f32 {
+    t * t * t * (t * (t * 6. - 15.) + 10.)
+}
+
+fn mix(x: f32, y: f32, a: f32) -> f32 {
+    x * (1. - a) + y * a
+}
+
+/// `cnoise(vec2)` from `classicnoise2D.glsl`
+fn cnoise2(p: Vec2) -> f32 {
+    let pi = mod289(
+        Vec4::new(p.x, p.y, p.x, p.y).floor()
+            + Vec4::new(0., 0., 1., 1.),
+    );
+    let pf = fract(Vec4::new(p.x, p.y, p.x, p.y))
+        - Vec4::new(0., 0., 1., 1.);
+    let (ix, iy) = (pi.xzxz(), pi.yyww());
+    let (fx, fy) = (pf.xzxz(), pf.yyww());
+
+    let i = permute(permute(ix) + iy);
+
+    let mut gx = fract(i * (1. / 41.)) * 2. - 1.;
+    let gy = gx.abs() - 0.5;
+    let tx = (gx + 0.5).floor();
+    gx -= tx;
+
+    let mut g00 = Vec2::new(gx.x, gy.x);
+    let mut g10 = Vec2::new(gx.y, gy.y);
+    let mut g01 = Vec2::new(gx.z, gy.z);
+    let mut g11 = Vec2::new(gx.w, gy.w);
+
+    let norm = taylor_inv_sqrt(Vec4::new(
+        g00.dot(g00),
+        g01.dot(g01),
+        g10.dot(g10),
+        g11.dot(g11),
+    ));
+    g00 *= norm.x;
+    g01 *= norm.y;
+    g10 *= norm.z;
+    g11 *= norm.w;
+
+    let n00 = g00.dot(Vec2::new(fx.x, fy.x));
+    let n10 = g10.dot(Vec2::new(fx.y, fy.y));
+    let n01 = g01.dot(Vec2::new(fx.z, fy.z));
+    let n11 = g11.dot(Vec2::new(fx.w, fy.w));
+
+    let fade_x = fade(pf.x);
+    let fade_y = fade(pf.y);
+    let n_x = Vec2::new(
+        mix(n00, n10, fade_x),
+        mix(n01, n11, fade_x),
+    );
+    let n_xy = mix(n_x.x, n_x.y, fade_y);
+    2.3 * n_xy
+}
+
+/// The gradients of one z layer of `cnoise(vec3)`
+fn cnoise3_gradients(ixy: Vec4) -> [Vec3; 4] {
+    let mut gx = ixy / 7.;
+    let mut gy = fract(gx.floor() / 7.) - 0.5;
+    gx = fract(gx);
+    let gz = Vec4::splat(0.5) - gx.abs() - gy.abs();
+    let sz = step(gz, Vec4::ZERO);
+    gx -= sz * (step(Vec4::ZERO, gx) - 0.5);
+    gy -= sz * (step(Vec4::ZERO, gy) - 0.5);
+
+    let g0 = Vec3::new(gx.x, gy.x, gz.x);
+    let g1 = Vec3::new(gx.y, gy.y, gz.y);
+    let g2 = Vec3::new(gx.z, gy.z, gz.z);
+    let g3 = Vec3::new(gx.w, gy.w, gz.w);
+    let norm = taylor_inv_sqrt(Vec4::new(
+        g0.dot(g0),
+        g2.dot(g2),
+        g1.dot(g1),
+        g3.dot(g3),
+    ));
+    [g0 * norm.x, g1 * norm.z, g2 * norm.y, g3 * norm.w]
+}
+
+/// `cnoise(vec3)` from `classicnoise3D.glsl`
+fn cnoise3(p: Vec3) -> f32 {
+    let pi0 = p.floor();
+    let pi1 = pi0 + 1.;
+    let pi0 = mod289(pi0.extend(0.)).truncate();
+    let pi1 = mod289(pi1.extend(0.)).truncate();
+    let pf0 = p - p.floor();
+    let pf1 = pf0 - 1.;
+    let ix = Vec4::new(pi0.x, pi1.x, pi0.x, pi1.x);
+    let iy = Vec4::new(pi0.y, pi0.y, pi1.y, pi1.y);
+    let iz0 = Vec4::splat(pi0.z);
+    let iz1 = Vec4::splat(pi1.z);
+
+    let ixy = permute(permute(ix) + iy);
+    let ixy0 = permute(ixy + iz0);
+    let ixy1 = permute(ixy + iz1);
+
+    let [g000, g100, g010, g110] = cnoise3_gradients(ixy0);
+    let [g001, g101, g011, g111] = cnoise3_gradients(ixy1);
+
+    let n000 = g000.dot(pf0);
+    let n100 = g100.dot(Vec3::new(pf1.x, pf0.y, pf0.z));
+    let n010 = g010.dot(Vec3::new(pf0.x, pf1.y, pf0.z));
+    let n110 = g110.dot(Vec3::new(pf1.x, pf1.y, pf0.z));
+    let n001 = g001.dot(Vec3::new(pf0.x, pf0.y, pf1.z));
+    let n101 = g101.dot(Vec3::new(pf1.x, pf0.y, pf1.z));
+    let n011 = g011.dot(Vec3::new(pf0.x, pf1.y, pf1.z));
+    let n111 = g111.dot(pf1);
+
+    let (fade_x, fade_y, fade_z) =
+        (fade(pf0.x), fade(pf0.y), fade(pf0.z));
+    let n_z = Vec4::new(
+        mix(n000, n001, fade_z),
+        mix(n100, n101, fade_z),
+        mix(n010, n011, fade_z),
+        mix(n110, n111, fade_z),
+    );
+    let n_yz = Vec2::new(
+        mix(n_z.x, n_z.z, fade_y),
+        mix(n_z.y, n_z.w, fade_y),
+    );
+    let n_xyz = mix(n_yz.x, n_yz.y, fade_x);
+    2.2 * n_xyz
+}
+
+/// `snoise(vec2)` from `noise2D.glsl`
+fn snoise2(v: Vec2) -> f32 {
+    let c = Vec4::new(
+        0.211324865405187,
+        0.366025403784439,
+        -0.577350269189626,
+        0.024390243902439,
+    );
+    let mut i = (v + v.dot(Vec2::splat(c.y))).floor();
+    let x0 = v - i + i.dot(Vec2::splat(c.x));
+
+    let i1 = if x0.x > x0.y {
+        Vec2::new(1., 0.)
+    } else {
+        Vec2::new(0., 1.)
+    };
+    let mut x12 = Vec4::new(x0.x, x0.y, x0.x, x0.y)
+        + Vec4::new(c.x, c.x, c.z, c.z);
+    x12.x -= i1.x;
+    x12.y -= i1.y;
+
+    i = mod289(i.extend(0.).extend(0.))
+        .truncate()
+        .truncate();
+    let p = permute(
+        permute(i.y + Vec4::new(0., i1.y, 1., 0.))
+            + i.x
+            + Vec4::new(0., i1.x, 1., 0.),
+    )
+    .truncate();
+
+    let mut m = (0.5
+        - Vec3::new(
+            x0.dot(x0),
+            x12.xy().dot(x12.xy()),
+            x12.zw().dot(x12.zw()),
+        ))
+    .max(Vec3::ZERO);
+    m *= m;
+    m *= m;
+
+    let x =
+        2. * fract((p * c.w).extend(0.)).truncate() - 1.;
+    let h = x.abs() - 0.5;
+    let ox = (x + 0.5).floor();
+    let a0 = x - ox;
+
+    m *= 1.79284291400159
+        - 0.85373472095314 * (a0 * a0 + h * h);
+
+    let g = Vec3::new(
+        a0.x * x0.x + h.x * x0.y,
+        a0.y * x12.x + h.y * x12.y,
+        a0.z * x12.z + h.z * x12.w,
+    );
+    130. * m.dot(g)
+}
+
+/// `snoise(vec3)` from `noise3D.glsl`
+fn snoise3(v: Vec3) -> f32 {
+    let c = Vec2::new(1. / 6., 1. / 3.);
+    let d = Vec4::new(0., 0.5, 1., 2.);
+
+    // First corner
+    let mut i = (v + v.dot(Vec3::splat(c.y))).floor();
+    let x0 = v - i + i.dot(Vec3::splat(c.x));
+
+    // Other corners
+    let g = step(
+        Vec4::new(x0.y, x0.z, x0.x, 0.),
+        x0.extend(0.),
+    )
+    .truncate();
+    let l = 1. - g;
+    let l_zxy = Vec3::new(l.z, l.x, l.y);
+    let i1 = g.min(l_zxy);
+    let i2 = g.max(l_zxy);
+
+    let x1 = x0 - i1 + c.x;
+    let x2 = x0 - i2 + c.y;
+    let x3 = x0 - d.y;
+
+    // Permutations
+    i = mod289(i.extend(0.)).truncate();
+    let p = permute(
+        permute(
+            permute(i.z + Vec4::new(0., i1.z, i2.z, 1.))
+                + i.y
+                + Vec4::new(0., i1.y, i2.y, 1.),
+        ) + i.x
+            + Vec4::new(0., i1.x, i2.x, 1.),
+    );
+
+    // Gradients: 7x7 points over a square, mapped onto an
+    // octahedron.
+    let n_ = 0.142857142857; // 1.0/7.0
+    let ns = n_ * Vec3::new(d.w, d.y, d.z)
+        - Vec3::new(d.x, d.z, d.x);
+
+    let j = p - 49. * (p * ns.z * ns.z).floor();
+
+    let x_ = (j * ns.z).floor();
+    let y_ = (j - 7. * x_).floor();
+
+    let x = x_ * ns.x + ns.y;
+    let y = y_ * ns.x + ns.y;
+    let h = 1. - x.abs() - y.abs();
+
+    let b0 = Vec4::new(x.x, x.y, y.x, y.y);
+    let b1 = Vec4::new(x.z, x.w, y.z, y.w);
+
+    let s0 = b0.floor() * 2. + 1.;
+    let s1 = b1.floor() * 2. + 1.;
+    let sh = -step(h, Vec4::ZERO);
+
+    let a0 = b0.xzyw() + s0.xzyw() * sh.xxyy();
+    let a1 = b1.xzyw() + s1.xzyw() * sh.zzww();
+
+    let mut p0 = Vec3::new(a0.x, a0.y, h.x);
+    let mut p1 = Vec3::new(a0.z, a0.w, h.y);
+    let mut p2 = Vec3::new(a1.x, a1.y, h.z);
+    let mut p3 = Vec3::new(a1.z, a1.w, h.w);
+
+    // Normalise gradients
+    let norm = taylor_inv_sqrt(Vec4::new(
+        p0.dot(p0),
+        p1.dot(p1),
+        p2.dot(p2),
+        p3.dot(p3),
+    ));
+    p0 *= norm.x;
+    p1 *= norm.y;
+    p2 *= norm.z;
+    p3 *= norm.w;
+
+    // Mix final noise value
+    let mut m = (0.6
+        - Vec4::new(
+            x0.dot(x0),
+            x1.dot(x1),
+            x2.dot(x2),
+            x3.dot(x3),
+        ))
+    .max(Vec4::ZERO);
+    m *= m;
+    42. * (m * m).dot(Vec4::new(
+        p0.dot(x0),
+        p1.dot(x1),
+        p2.dot(x2),
+        p3.dot(x3),
+    ))
+}
+
+fn assert_matches_reference(
+    name: &str,
+    samples: impl Iterator<Item = (String, f32, f32)>,
+) {
+    let mut min = f32::MAX;
+    let mut max = f32::MIN;
+    for (point, port, reference) in samples {
+        assert!(
+            (port - reference).abs() <= TOLERANCE,
+            "{name}({point}) gives {port}, the GLSL \
+             {reference}",
+        );
+        min = min.min(reference);
+        max = max.max(reference);
+    }
+    // make sure the grid samples something more than a constant
+    assert!(max - min > 0.5, "{name} spans {min}..{max}");
+}
+
+#[test]
+fn perlin_noise_2d_matches_the_glsl() {
+    assert_matches_reference(
+        "perlin_noise_2d",
+        grid_2d().map(|p| p + SHIFT).map(|p| {
+            (p.to_string(), perlin_noise_2d(p), cnoise2(p))
+        }),
+    );
+}
+
+#[test]
+fn perlin_noise_3d_matches_the_glsl() {
+    assert_matches_reference(
+        "perlin_noise_3d",
+        grid_3d().map(|p| p + SHIFT).map(|p| {
+            (p.to_string(), perlin_noise_3d(p), cnoise3(p))
+        }),
+    );
+}
+
+#[test]
+fn simplex_noise_2d_matches_the_glsl() {
+    assert_matches_reference(
+        "simplex_noise_2d",
+        grid_2d().map(|p| p + SHIFT).map(|p| {
+            (p.to_string(), simplex_noise_2d(p), snoise2(p))
+        }),
+    );
+}
+
+#[test]
+fn simplex_noise_3d_matches_the_glsl() {
+    assert_matches_reference(
+        "simplex_noise_3d",
+        grid_3d().map(|p| p + SHIFT).map(|p| {
+            (p.to_string(), simplex_noise_3d(p), snoise3(p))
+        }),
+    );
+}
+
+/// Dense samples over a few cells, negative ones included
+fn dense_2d() -> impl Iterator<Item = Vec2> {
+    (-100..100).flat_map(|y| {
+        (-100..100).map(move |x| {
+            Vec2::new(x as f32, y as f32) * 0.037
+        })
+    })
+}
+
+fn dense_3d() -> impl Iterator<Item = Vec3> {
+    (-20..20).flat_map(|z| {
+        dense_2d()
+            .step_by(7)
+            .map(move |p| p.extend(z as f32 * 0.071))
+    })
+}
+
+#[test]
+fn noise_stays_in_range() {
+    // Gustavson scales the 3D Perlin noise to "approximately"
+    // [-1, 1], and the Taylor inverse square root lets it overshoot
+    let ranges: [(&str, f32, Vec<f32>); 4] = [
+        (
+            "perlin_noise_2d",
+            1.,
+            dense_2d().map(perlin_noise_2d).collect(),
+        ),
+        (
+            "perlin_noise_3d",
+            1.05,
+            dense_3d().map(perlin_noise_3d).collect(),
+        ),
+        (
+            "simplex_noise_2d",
+            1.,
+            dense_2d().map(simplex_noise_2d).collect(),
+        ),
+        (
+            "simplex_noise_3d",
+            1.,
+            dense_3d().map(simplex_noise_3d).collect(),
+        ),
+    ];
+    for (name, bound, values) in ranges {
+        let min =
+            values.iter().copied().fold(f32::MAX, f32::min);
+        let max =
+            values.iter().copied().fold(f32::MIN, f32::max);
+        assert!(
+            -bound <= min && max <= bound,
+            "{name} spans {min}..{max}",
+        );
+        assert!(
+            max - min > 1.,
+            "{name} spans {min}..{max}"
+        );
+    }
+}
+
+#[test]
+fn perlin_noise_is_zero_on_the_lattice() {
+    for y in -5..5 {
+        for x in -5..5 {
+            let p = Vec2::new(x as f32, y as f32);
+            assert_eq!(perlin_noise_2d(p), 0., "at {p}");
+            for z in [-3., 0., 2.] {
+                let p = p.extend(z);
+                assert_eq!(
+                    perlin_noise_3d(p),
+                    0.,
+                    "at {p}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,191 @@
+mod common;
+
+use bevy::{
+    prelude::*, render::render_resource::ShaderLoader,
+};
+use bevy_shader_utils::*;
+use common::*;
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin::default())
+        .add_asset::<Shader>()
+        .init_asset_loader::<ShaderLoader>()
+        .add_plugin(ShaderUtilsPlugin);
+    // with hot-reload the modules load on the io task pool
+    for _ in 0..500 {
+        let shader_utils =
+            app.world.resource::<ShaderUtils>();
+        let shaders =
+            app.world.resource::<Assets<Shader>>();
+        if shader_utils
+            .iter()
+            .all(|module| shaders.contains(&module.handle))
+        {
+            break;
+        }
+        app.update();
+        std::thread::sleep(
+            std::time::Duration::from_millis(10),
+        );
+    }
+    app
+}
+
+/// Whether the asset behind `import_path` is `wgsl`
+fn assert_registered(
+    app: &App,
+    import_path: &str,
+    wgsl: &str,
+) {
+    let shader_utils = app.world.resource::<ShaderUtils>();
+    let shaders = app.world.resource::<Assets<Shader>>();
+    let handle = shader_utils.handle(import_path).unwrap();
+    let shader = shaders.get(&handle).unwrap();
+    // the source is private, Debug shows it all
+    assert_eq!(
+        format!("{shader:?}"),
+        format!(
+            "{:?}",
+            Shader::from_wgsl(wgsl.to_string())
+                .with_import_path(import_path)
+        ),
+    );
+    assert_eq!(
+        shader_utils.get(import_path).unwrap().source,
+        wgsl
+    );
+}
+
+#[test]
+fn every_built_in_module_is_registered() {
+    let app = app();
+    let shader_utils = app.world.resource::<ShaderUtils>();
+    let registered: Vec<_> = shader_utils
+        .iter()
+        .map(|module| module.import_path.clone())
+        .collect();
+    let expected: Vec<_> = import_paths()
+        .into_iter()
+        .map(|(path, _)| path)
+        .collect();
+    assert_eq!(registered, expected);
+
+    for (path, wgsl) in import_paths() {
+        assert_registered(&app, &path, &wgsl);
+    }
+}
+
+#[test]
+fn user_modules_get_their_own_namespace() {
+    let mut app = app();
+    let wgsl = "fn answer() -> f32 { return 42.; }";
+    app.world.resource_scope(
+        |world, mut shader_utils: Mut<ShaderUtils>| {
+            let mut shaders =
+                world.resource_mut::<Assets<Shader>>();
+            shader_utils.register(
+                &mut shaders,
+                "my_game",
+                "answer",
+                wgsl,
+            );
+        },
+    );
+    assert_registered(&app, "my_game::answer", wgsl);
+}
+
+#[test]
+fn built_in_modules_can_be_overridden() {
+    let mut app = app();
+    let path = "bevy_shader_utils::voronoise";
+    let handle = app
+        .world
+        .resource::<ShaderUtils>()
+        .handle(path)
+        .unwrap();
+    let wgsl = "fn voronoise(p: vec2<f32>, u: f32, v: f32) -> f32 \
+                { return 0.; }";
+    app.world.resource_scope(
+        |world, mut shader_utils: Mut<ShaderUtils>| {
+            let mut shaders =
+                world.resource_mut::<Assets<Shader>>();
+            let overridden = shader_utils.register(
+                &mut shaders,
+                NAMESPACE,
+                "voronoise",
+                wgsl,
+            );
+            // shaders importing it keep pointing at the same handle
+            assert_eq!(overridden, handle);
+        },
+    );
+    assert_registered(&app, path, wgsl);
+    assert_eq!(
+        app.world.resource::<ShaderUtils>().iter().count(),
+        built_in_modules().count(),
+    );
+}
+
+#[cfg(feature = "hot-reload")]
+#[test]
+fn hot_reload_loads_the_modules_from_disk() {
+    let app = app();
+    let shader_utils = app.world.resource::<ShaderUtils>();
+    let asset_server = app.world.resource::<AssetServer>();
+    // the generated fbm modules have no file to load
+    for (name, _) in MODULES {
+        let module = shader_utils
+            .get(&format!("{NAMESPACE}::{name}"))
+            .unwrap();
+        assert!(asset_server
+            .get_handle_path(&module.handle)
+            .is_some());
+    }
+}
+
+#[cfg(feature = "hot-reload")]
+#[test]
+fn overrides_survive_hot_reload() {
+    let mut app = app();
+    let path = "bevy_shader_utils::voronoise";
+    let wgsl = "fn voronoise(p: vec2<f32>, u: f32, v: f32) -> f32 \
+                { return 0.; }";
+    app.world.resource_scope(
+        |world, mut shader_utils: Mut<ShaderUtils>| {
+            let mut shaders =
+                world.resource_mut::<Assets<Shader>>();
+            shader_utils.register(
+                &mut shaders,
+                NAMESPACE,
+                "voronoise",
+                wgsl,
+            );
+        },
+    );
+    // long enough for unused assets to be freed
+    for _ in 0..5 {
+        app.update();
+    }
+    assert_registered(&app, path, wgsl);
+
+    // as if the file changed on disk
+    let handle = app
+        .world
+        .resource::<ShaderUtils>()
+        .handle(path)
+        .unwrap();
+    let asset_server =
+        app.world.resource::<AssetServer>().clone();
+    asset_server.reload_asset(
+        asset_server.get_handle_path(&handle).unwrap(),
+    );
+    for _ in 0..50 {
+        app.update();
+        std::thread::sleep(
+            std::time::Duration::from_millis(10),
+        );
+    }
+    assert_registered(&app, path, wgsl);
+}
@@ -0,0 +1,326 @@
+mod common;
+
+use bevy::math::{Vec2, Vec3};
+use common::{interpreter::*, *};
+
+fn assert_close(actual: f32, expected: f32, what: &str) {
+    assert!(
+        (actual - expected).abs() < 1e-4,
+        "{what} is {actual}, expected {expected}",
+    );
+}
+
+#[test]
+fn shapes_2d_measure_their_distance() {
+    let shader = shader(&["bevy_shader_utils::sdf_2d"]);
+    let call = |name: &str, arguments: &[Value]| {
+        shader.call(name, arguments).f32()
+    };
+    let circle =
+        |p: Vec2| call("sdCircle", &[p.into(), 1.0.into()]);
+    assert_close(circle(Vec2::ZERO), -1., "circle center");
+    assert_close(
+        circle(Vec2::new(3., 4.)),
+        4.,
+        "circle at (3, 4)",
+    );
+
+    let half_size = Vec2::new(2., 1.);
+    let rect = |p: Vec2| {
+        call("sdBox2", &[p.into(), half_size.into()])
+    };
+    assert_close(rect(Vec2::ZERO), -1., "box center");
+    assert_close(rect(Vec2::new(5., 0.)), 3., "box side");
+    assert_close(rect(Vec2::new(5., 5.)), 5., "box corner");
+
+    let segment = call(
+        "sdSegment2",
+        &[
+            Vec2::new(1., 2.).into(),
+            Vec2::ZERO.into(),
+            Vec2::new(3., 0.).into(),
+        ],
+    );
+    assert_close(segment, 2., "segment");
+
+    let triangle = |p: Vec2| {
+        call(
+            "sdEquilateralTriangle",
+            &[p.into(), 1.0.into()],
+        )
+    };
+    assert!(triangle(Vec2::ZERO) < 0.);
+    // the bottom side is at y = -1 / sqrt(3)
+    assert_close(
+        triangle(Vec2::new(0., -1. / 3f32.sqrt() - 0.5)),
+        0.5,
+        "triangle bottom",
+    );
+
+    let hexagon = |p: Vec2| {
+        call("sdHexagon", &[p.into(), 1.0.into()])
+    };
+    assert_close(
+        hexagon(Vec2::ZERO),
+        -1.,
+        "hexagon center",
+    );
+    assert_close(
+        hexagon(Vec2::new(0., 3.)),
+        2.,
+        "hexagon top",
+    );
+
+    let star = |p: Vec2| {
+        call("sdStar5", &[p.into(), 1.0.into(), 0.5.into()])
+    };
+    assert!(star(Vec2::ZERO) < 0.);
+    assert_close(star(Vec2::new(0., 2.)), 1., "star tip");
+}
+
+#[test]
+fn shapes_3d_measure_their_distance() {
+    let shader = shader(&["bevy_shader_utils::sdf_3d"]);
+    let call = |name: &str, arguments: &[Value]| {
+        shader.call(name, arguments).f32()
+    };
+    assert_close(
+        call(
+            "sdSphere",
+            &[Vec3::new(0., 3., 4.).into(), 2.0.into()],
+        ),
+        3.,
+        "sphere",
+    );
+    assert_close(
+        call(
+            "sdBox3",
+            &[
+                Vec3::new(3., 3., 0.).into(),
+                Vec3::ONE.into(),
+            ],
+        ),
+        8f32.sqrt(),
+        "box edge",
+    );
+    let torus = Vec2::new(2., 0.5);
+    assert_close(
+        call(
+            "sdTorus",
+            &[Vec3::new(2., 0., 0.).into(), torus.into()],
+        ),
+        -0.5,
+        "torus tube",
+    );
+    assert_close(
+        call("sdTorus", &[Vec3::ZERO.into(), torus.into()]),
+        1.5,
+        "torus hole",
+    );
+    assert_close(
+        call(
+            "sdCapsule",
+            &[
+                Vec3::new(0., 5., 1.).into(),
+                Vec3::ZERO.into(),
+                Vec3::new(0., 2., 0.).into(),
+                0.5.into(),
+            ],
+        ),
+        10f32.sqrt() - 0.5,
+        "capsule end",
+    );
+    assert_close(
+        call(
+            "sdCylinder",
+            &[
+                Vec3::new(3., 0., 0.).into(),
+                1.0.into(),
+                1.0.into(),
+            ],
+        ),
+        2.,
+        "cylinder side",
+    );
+    assert_close(
+        call(
+            "sdPlane",
+            &[
+                Vec3::new(1., 4., 1.).into(),
+                Vec3::Y.into(),
+                1.0.into(),
+            ],
+        ),
+        5.,
+        "plane",
+    );
+}
+
+#[test]
+fn operators_combine_distances() {
+    let shader =
+        shader(&["bevy_shader_utils::sdf_operators"]);
+    let call = |name: &str, arguments: &[f32]| {
+        let arguments: Vec<Value> =
+            arguments.iter().map(|&a| a.into()).collect();
+        shader.call(name, &arguments).f32()
+    };
+    assert_close(call("opUnion", &[1., -2.]), -2., "union");
+    assert_close(
+        call("opIntersection", &[1., -2.]),
+        1.,
+        "intersection",
+    );
+    assert_close(
+        call("opSubtraction", &[-1., -0.5]),
+        0.5,
+        "subtraction",
+    );
+    assert_close(
+        call("opRound", &[1., 0.25]),
+        0.75,
+        "round",
+    );
+    assert_close(
+        call("opOnion", &[-1., 0.25]),
+        0.75,
+        "onion",
+    );
+
+    for (a, b) in [(0.1, 0.2), (-0.3, 0.05), (2., 3.)] {
+        let k = 0.5;
+        let union = call("opSmoothUnion", &[a, b, k]);
+        let intersection =
+            call("opSmoothIntersection", &[a, b, k]);
+        let subtraction =
+            call("opSmoothSubtraction", &[a, b, k]);
+        // blends pull the surface out, never in
+        assert!(
+            union <= a.min(b) + 1e-6,
+            "{a} {b}: {union}"
+        );
+        assert!(intersection >= a.max(b) - 1e-6);
+        assert!(subtraction >= a.max(-b) - 1e-6);
+        // and far apart, they're the sharp ones
+        if (a - b).abs() >= k {
+            assert_close(union, a.min(b), "smooth union");
+        }
+    }
+}
+
+#[test]
+fn repetition_folds_space_into_one_cell() {
+    let shader =
+        shader(&["bevy_shader_utils::sdf_operators"]);
+    let spacing = Vec3::new(2., 3., 4.);
+    let repeat = |p: Vec3| {
+        Vec3::from_slice(
+            &shader
+                .call(
+                    "opRepeat3",
+                    &[p.into(), spacing.into()],
+                )
+                .f32s(),
+        )
+    };
+    let p = Vec3::new(0.3, -0.4, 1.1);
+    assert!(repeat(p).abs_diff_eq(p, 1e-5));
+    for cell in
+        [Vec3::new(1., 0., 0.), Vec3::new(-2., 3., -1.)]
+    {
+        assert!(
+            repeat(p + cell * spacing).abs_diff_eq(p, 1e-4)
+        );
+    }
+
+    let limit = Vec2::new(1., 2.);
+    let spacing = Vec2::splat(2.);
+    let repeat_limited = |p: Vec2| {
+        Vec2::from_slice(
+            &shader
+                .call(
+                    "opRepeatLimited2",
+                    &[
+                        p.into(),
+                        spacing.into(),
+                        limit.into(),
+                    ],
+                )
+                .f32s(),
+        )
+    };
+    let p = Vec2::new(0.3, -0.4);
+    assert!(repeat_limited(p + Vec2::new(2., -4.))
+        .abs_diff_eq(p, 1e-5));
+    // past the last copy, space isn't folded back
+    assert!(repeat_limited(p + Vec2::new(4., 0.))
+        .abs_diff_eq(p + Vec2::new(2., 0.), 1e-5));
+}
+
+#[test]
+fn raymarching_finds_the_scene() {
+    // the test scene is a unit sphere
+    let shader = shader(&["bevy_shader_utils::raymarch"]);
+    let origin = Vec3::new(0., 0., -5.);
+    let hit = shader
+        .call(
+            "raymarch",
+            &[
+                origin.into(),
+                Vec3::Z.into(),
+                100.0.into(),
+                Value::I32(64),
+            ],
+        )
+        .f32();
+    assert!((hit - 4.).abs() < 1e-3, "hit at {hit}");
+
+    let miss = shader
+        .call(
+            "raymarch",
+            &[
+                origin.into(),
+                Vec3::Y.into(),
+                100.0.into(),
+                Value::I32(64),
+            ],
+        )
+        .f32();
+    assert_eq!(miss, -1.);
+
+    let normal = Vec3::from_slice(
+        &shader
+            .call(
+                "raymarchNormal",
+                &[Vec3::new(0., 0., -1.).into()],
+            )
+            .f32s(),
+    );
+    assert!(normal.abs_diff_eq(-Vec3::Z, 1e-3), "{normal}");
+
+    // lit from above, in the shadow of the sphere below it
+    let light = |p: Vec3| {
+        shader
+            .call(
+                "raymarchSoftShadow",
+                &[
+                    p.into(),
+                    Vec3::Y.into(),
+                    0.01.into(),
+                    10.0.into(),
+                    8.0.into(),
+                ],
+            )
+            .f32()
+    };
+    assert_eq!(light(Vec3::new(0., 1.01, 0.)), 1.);
+    assert!(light(Vec3::new(0., -2., 0.)) < 0.01);
+
+    let occlusion = shader
+        .call(
+            "raymarchAmbientOcclusion",
+            &[Vec3::new(0., 1., 0.).into(), Vec3::Y.into()],
+        )
+        .f32();
+    assert!(occlusion > 0.9, "{occlusion}");
+}
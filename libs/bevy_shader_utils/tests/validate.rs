@@ -0,0 +1,56 @@
+mod common;
+
+use common::*;
+
+const CUSTOM_MATERIAL: &str =
+    include_str!("../assets/shaders/custom_material.wgsl");
+const SDF_MATERIAL: &str =
+    include_str!("../assets/shaders/sdf_material.wgsl");
+const MY_MATERIAL: &str = include_str!(
+    "../../../sandbox/custom-shader/assets/my_material.wgsl"
+);
+
+#[test]
+fn every_module_validates() {
+    let modules = ShaderModules::default();
+    for (path, _) in import_paths() {
+        let path = path.as_str();
+        let imports = with_dependencies(&[path]);
+        if let Err(err) =
+            validate_imports(&modules, &imports)
+        {
+            panic!("{path}:\n{err}");
+        }
+    }
+}
+
+#[test]
+fn modules_need_nothing_but_their_dependencies() {
+    let modules = ShaderModules::default();
+    for (path, _) in import_paths() {
+        let path = path.as_str();
+        assert_eq!(
+            validate_imports(&modules, &[path]).is_ok(),
+            with_dependencies(&[path]) == [path],
+            "{path}",
+        );
+    }
+}
+
+#[test]
+fn materials_validate() {
+    let modules = ShaderModules::with_bevy();
+    let shader_defs = vec!["VERTEX_UVS".to_string()];
+    for (name, source) in [
+        ("custom_material.wgsl", CUSTOM_MATERIAL),
+        ("sdf_material.wgsl", SDF_MATERIAL),
+        ("my_material.wgsl", MY_MATERIAL),
+    ] {
+        let result = modules
+            .process(source, &shader_defs)
+            .and_then(|source| validate(&source));
+        if let Err(err) = result {
+            panic!("{name}:\n{err}");
+        }
+    }
+}
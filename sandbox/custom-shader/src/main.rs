@@ -6,12 +6,21 @@ mod utils;
 use std::f32::consts::PI;
 
 use bevy::{log::LogPlugin, prelude::*};
+use bevy_basic_camera::{CameraSystem, InputContexts};
 use material::MyMaterial;
 use mesh_data::CubeSphere;
 use utils::OrbitCamera;
 
+#[cfg(debug_assertions)]
+use bevy::window::WindowId;
+#[cfg(debug_assertions)]
+use bevy_basic_camera::{CameraInputAccess, FrameEntity};
+#[cfg(debug_assertions)]
+use bevy_inspector_egui::bevy_egui::EguiContext;
 #[cfg(debug_assertions)]
 use bevy_inspector_egui::quick::WorldInspectorPlugin;
+#[cfg(debug_assertions)]
+use mesh_data::CubeSphereInfo;
 
 const CLEAR: Color = Color::GRAY;
 const ASPECT_RATIO: f32 = 16.0 / 9.0;
@@ -47,22 +56,39 @@ fn main() {
         //
         // Plugins
         .add_plugin(MaterialPlugin::<MyMaterial>::default())
-        .add_plugin(OrbitCamera::default())
+        .add_plugin(OrbitCamera)
         .add_plugin(mesh_data::CubeSpherePlugin)
         //
         // Systems
         .add_startup_system(setup)
-        .add_system(move_components);
+        .add_system(toggle_object_manipulation.before(CameraSystem::Input))
+        .add_system(move_components.before(CameraSystem::Input));
 
     #[cfg(debug_assertions)]
     application
         .add_plugin(WorldInspectorPlugin)
-        .add_plugin(mesh_data::InspectPlugin::<CubeSphere>::default());
+        .add_plugin(mesh_data::InspectPlugin::<CubeSphere>::default())
+        .add_system(frame_inspected)
+        .add_system(
+            inspector_context
+                .before(toggle_object_manipulation)
+                .before(CameraSystem::Input),
+        );
 
     application.run();
 }
 
-fn setup(mut commands: Commands, mut materials: ResMut<Assets<StandardMaterial>>) {
+const OBJECT_MANIPULATION: &str = "object-manipulation";
+#[cfg(debug_assertions)]
+const INSPECTOR: &str = "inspector";
+
+fn setup(
+    mut commands: Commands,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut contexts: ResMut<InputContexts>,
+) {
+    contexts.push(InputContexts::CAMERA);
+
     commands
         .spawn(MaterialMeshBundle {
             material: materials.add(Color::ORANGE.into()),
@@ -90,17 +116,33 @@ fn setup(mut commands: Commands, mut materials: ResMut<Assets<StandardMaterial>>
         .insert(Name::new("Light"));
 }
 
-//// Misc Plugins //////////////////////////////////////////////////////////////////////////////////
+// Misc Plugins ////////////////////////////////////////////////////////////////////////////////////////
 
 #[derive(Component, Reflect, Default)]
 #[reflect(Component)]
 struct Movable;
 
+/// Press G to hand WASD over from the camera to the [`Movable`]s and back
+fn toggle_object_manipulation(input: Res<Input<KeyCode>>, mut contexts: ResMut<InputContexts>) {
+    if !input.just_pressed(KeyCode::G) {
+        return;
+    }
+    if contexts.top() == Some(OBJECT_MANIPULATION) {
+        contexts.remove(OBJECT_MANIPULATION);
+    } else if contexts.is_active(InputContexts::CAMERA) {
+        contexts.push(OBJECT_MANIPULATION);
+    }
+}
+
 fn move_components(
     input: Res<Input<KeyCode>>,
     time: Res<Time>,
+    contexts: Res<InputContexts>,
     mut query: Query<&mut Transform, With<Movable>>,
 ) {
+    if !contexts.is_active(OBJECT_MANIPULATION) {
+        return;
+    }
     for mut transform in query.iter_mut() {
         let mut direction = Vec3::ZERO;
 
@@ -120,3 +162,35 @@ fn move_components(
         transform.translation += time.delta_seconds() * direction;
     }
 }
+
+/// Press F to have the camera frame the inspected [`CubeSphere`]
+#[cfg(debug_assertions)]
+fn frame_inspected(
+    input: Res<Input<KeyCode>>,
+    access: CameraInputAccess,
+    mut frame_events: EventWriter<FrameEntity>,
+    query: Query<Entity, With<CubeSphereInfo>>,
+) {
+    if !access.keyboard() {
+        return;
+    }
+    if input.just_pressed(KeyCode::F) {
+        if let Some(entity) = query.iter().next() {
+            frame_events.send(FrameEntity(entity));
+        }
+    }
+}
+
+/// Put the inspector on top of the input contexts while it's being used
+#[cfg(debug_assertions)]
+fn inspector_context(mut egui_context: ResMut<EguiContext>, mut contexts: ResMut<InputContexts>) {
+    let Some(ctx) = egui_context.try_ctx_for_window_mut(WindowId::primary()) else {
+        return;
+    };
+    let wants_input = ctx.wants_pointer_input() || ctx.wants_keyboard_input();
+    if wants_input && contexts.top() != Some(INSPECTOR) {
+        contexts.push(INSPECTOR);
+    } else if !wants_input && contexts.contains(INSPECTOR) {
+        contexts.remove(INSPECTOR);
+    }
+}
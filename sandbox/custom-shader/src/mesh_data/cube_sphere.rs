@@ -3,7 +3,7 @@ use crate::mesh_data::VertexData;
 use bevy::{
     pbr::wireframe::Wireframe,
     prelude::*,
-    render::{mesh::Indices, render_resource::PrimitiveTopology},
+    render::{mesh::Indices, primitives::Aabb, render_resource::PrimitiveTopology},
 };
 use bevy_inspector_egui::prelude::*;
 use itertools::Itertools;
@@ -11,7 +11,7 @@ use itertools::Itertools;
 type VertexTemplate =
     itertools::Product<std::ops::RangeInclusive<u32>, std::ops::RangeInclusive<u32>>;
 
-//// Components ////////////////////////////////////////////////////////////////////////////////////
+// Components //////////////////////////////////////////////////////////////////////////////////////////
 
 #[derive(Debug, Component, Reflect, InspectorOptions, PartialEq, Copy, Clone)]
 #[reflect(Component, InspectorOptions)]
@@ -63,12 +63,11 @@ impl ProceduralShape for CubeSphere {
 
         for face_direction in faces {
             let mut face_vertices =
-                create_face_vertices(&self, face_direction, vertex_template.clone());
+                create_face_vertices(self, face_direction, vertex_template.clone());
 
-            let mut face_indices =
-                create_face_indices(&self, index_offset, vertex_template.clone());
+            let mut face_indices = create_face_indices(self, index_offset, vertex_template.clone());
 
-            index_offset = face_indices.iter().max().unwrap().clone() + 1;
+            index_offset = *face_indices.iter().max().unwrap() + 1;
 
             vertices_vec.append(&mut face_vertices);
             indices_vec.append(&mut face_indices);
@@ -135,7 +134,7 @@ impl Inspectable<CubeSphere> for CubeSphere {
             // Check is data outdated
             if *cube_sphere_data != debug_info.old_data {
                 debug_info.outdated = true;
-                debug_info.old_data = cube_sphere_data.clone();
+                debug_info.old_data = *cube_sphere_data;
             }
 
             if !debug_info.outdated {
@@ -148,6 +147,11 @@ impl Inspectable<CubeSphere> for CubeSphere {
 
                 debug_info.outdated = false;
 
+                // have the bounds recalculated for the new mesh
+                if let Some(mut entity) = commands.get_entity(entt) {
+                    entity.remove::<Aabb>();
+                }
+
                 match cube_mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
                     Some(val) => debug_info.num_vertices = val.len(),
                     None => debug_info.num_vertices = 0,
@@ -171,7 +175,7 @@ impl Default for CubeSphere {
     }
 }
 
-//// Plugins ///////////////////////////////////////////////////////////////////////////////////////
+// Plugins /////////////////////////////////////////////////////////////////////////////////////////////
 
 #[allow(dead_code)]
 pub struct CubeSpherePlugin;
@@ -199,7 +203,7 @@ fn init_cube_sphere(
     }
 }
 
-//// Helpers ///////////////////////////////////////////////////////////////////////////////////////
+// Helpers /////////////////////////////////////////////////////////////////////////////////////////////
 
 fn create_face_vertices(
     cube_sphere: &CubeSphere,
@@ -286,9 +290,7 @@ fn create_face_indices(
     let out = vertex_template
         .enumerate()
         .filter_map(|(i, (x, y))| {
-            if y >= face.resolution {
-                None
-            } else if x >= face.resolution {
+            if y >= face.resolution || x >= face.resolution {
                 None
             } else {
                 let index = i as u32 + index_offset;
@@ -1,120 +1,101 @@
-use crate::ASPECT_RATIO;
-use bevy::{input::mouse::MouseMotion, prelude::*};
+use crate::mesh_data::CubeSphere;
+use bevy::prelude::*;
+use bevy_basic_camera::{
+    CameraBookmarksPlugin, CameraCollision, CameraController, CameraControllerPlugin,
+    CameraInputAccess, CameraMode, CameraPath, CameraPathPlugin, CameraSettingsPlugin, WalkSurface,
+};
 use std::f32::consts::PI;
 
-/// Tags an entity as capable of panning and orbiting.
-#[derive(Component)]
-pub struct OrbitCamera {
-    /// The "focus point" to orbit around. It is automatically updated when panning the camera
-    pub focus: Vec3,
-    pub radius: f32,
-    pub upside_down: bool,
-}
-
-impl Default for OrbitCamera {
-    fn default() -> Self {
-        OrbitCamera {
-            focus: Vec3::ZERO,
-            radius: 5.0,
-            upside_down: false,
-        }
-    }
-}
+/// Spawns a camera orbiting the origin.
+/// Orbit with right mouse click, pan with middle mouse click, zoom with scroll wheel,
+/// press tab to cycle through the camera modes and ctrl + number to bookmark the view.
+/// Press T to start or stop a turntable around the focus, P pauses it.
+/// O toggles between perspective and orthographic projection.
+/// On touch screens one finger orbits, two fingers pan, pinch to zoom and turn to orbit.
+/// In walk mode WASD walks around the planet and space jumps.
+/// The camera can't zoom or orbit into the planet or other meshes.
+/// Its controller can be tweaked in the inspector, F5 saves the sensitivity, speeds and
+/// bindings to `camera_settings.ron`.
+#[derive(Default)]
+pub struct OrbitCamera;
 
 impl Plugin for OrbitCamera {
     fn build(&self, app: &mut App) {
-        app.add_startup_system(spawn_camera)
-            .add_system(pan_orbit_camera);
-    }
-}
-
-/// Pan the camera with middle mouse click, zoom with scroll wheel, orbit with right mouse click.
-fn pan_orbit_camera(
-    windows: Res<Windows>,
-    mut ev_motion: EventReader<MouseMotion>,
-    input_mouse: Res<Input<MouseButton>>,
-    mut query: Query<(&mut OrbitCamera, &mut Transform)>,
-) {
-    // change input mapping for orbit and panning here
-    let orbit_button = MouseButton::Right;
-    let mut rotation_move = Vec2::ZERO;
-    let mut orbit_button_changed = false;
-
-    if input_mouse.pressed(orbit_button) {
-        for ev in ev_motion.iter() {
-            rotation_move += ev.delta;
-        }
-    }
-
-    if input_mouse.just_released(orbit_button) || input_mouse.just_pressed(orbit_button) {
-        orbit_button_changed = true;
-    }
-
-    for (mut pan_orbit, mut transform) in query.iter_mut() {
-        if orbit_button_changed {
-            // only check for upside down when orbiting started or ended this frame
-            // if the camera is "upside" down, panning horizontally would be inverted, so invert the input to make it correct
-            let up = transform.rotation * Vec3::Y;
-            pan_orbit.upside_down = up.y <= 0.0;
-        }
-
-        let mut any = false;
-        if rotation_move.length_squared() > 0.0 {
-            any = true;
-            let window = get_primary_window_size(&windows);
-            let delta_x = {
-                let delta = rotation_move.x / window.x * std::f32::consts::PI * 2.0;
-                if pan_orbit.upside_down {
-                    -delta
-                } else {
-                    delta
-                }
-            };
-            let delta_y = rotation_move.y / window.y * std::f32::consts::PI;
-            let yaw = Quat::from_rotation_y(-delta_x);
-            let pitch = Quat::from_rotation_x(-delta_y);
-            transform.rotation = yaw * transform.rotation; // rotate around global y axis
-            transform.rotation = transform.rotation * pitch; // rotate around local x axis
-                                                             //
-        }
-
-        if any {
-            // emulating parent/child to make the yaw/y-axis rotation behave like a turntable
-            // parent = x and y rotation
-            // child = z-offset
-            let rot_matrix = Mat3::from_quat(transform.rotation);
-            transform.translation =
-                pan_orbit.focus + rot_matrix.mul_vec3(Vec3::new(0.0, 0.0, pan_orbit.radius));
-        }
+        app.add_plugin(CameraControllerPlugin)
+            .add_plugin(CameraBookmarksPlugin)
+            .add_plugin(CameraPathPlugin::default())
+            .add_plugin(CameraSettingsPlugin)
+            .add_startup_system(spawn_camera)
+            .add_system(toggle_turntable)
+            .add_system(sync_walk_surface);
     }
 }
 
 /// Spawn a camera like this
 fn spawn_camera(mut commands: Commands) {
     let translation = Vec3::new(0.0, 0.0, 7.0);
-    let radius = translation.length();
 
     commands
         .spawn((
             Camera3dBundle {
                 projection: Projection::Perspective(PerspectiveProjection {
                     fov: PI / 8.0,
-                    aspect_ratio: ASPECT_RATIO,
                     ..Default::default()
                 }),
                 transform: Transform::from_translation(translation).looking_at(Vec3::ZERO, Vec3::Y),
                 ..Default::default()
             },
-            OrbitCamera {
-                radius,
+            CameraController {
+                mode: CameraMode::Orbit,
+                mouse_key_enable_mouse: MouseButton::Right,
+                walk_speed: 0.5,
+                run_speed: 1.5,
+                eye_height: 0.02,
+                gravity: 0.5,
+                jump_speed: 0.2,
+                ..Default::default()
+            },
+            CameraCollision {
+                radius: 0.02,
+                min_surface_distance: 0.05,
                 ..Default::default()
             },
         ))
         .insert(Name::from("Player Orbit Camera"));
 }
 
-fn get_primary_window_size(windows: &Res<Windows>) -> Vec2 {
-    let window = windows.get_primary().unwrap();
-    let window = Vec2::new(window.width() as f32, window.height() as f32);
-    window
+fn toggle_turntable(
+    mut commands: Commands,
+    input: Res<Input<KeyCode>>,
+    access: CameraInputAccess,
+    query: Query<(Entity, &Transform, &CameraController, Option<&CameraPath>)>,
+) {
+    if !access.keyboard() || !input.just_pressed(KeyCode::T) {
+        return;
+    }
+
+    for (entity, transform, controller, path) in query.iter() {
+        if path.is_some() {
+            commands.entity(entity).remove::<CameraPath>();
+        } else {
+            commands
+                .entity(entity)
+                .insert(CameraPath::turntable(transform, controller, 10.0));
+        }
+    }
+}
+
+/// Keeps the planet walkable at whatever radius it's given.
+/// `MeshNoise` is only a stub that doesn't displace the mesh yet, so
+/// walking follows the bare radius. Once it does, its height goes in
+/// here through `WalkSurface::with_height`.
+fn sync_walk_surface(
+    mut commands: Commands,
+    query: Query<(Entity, &CubeSphere), Changed<CubeSphere>>,
+) {
+    for (entity, cube_sphere) in query.iter() {
+        commands
+            .entity(entity)
+            .insert(WalkSurface::new(cube_sphere.radius));
+    }
 }